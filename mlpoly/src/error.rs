@@ -0,0 +1,28 @@
+//! Error types for multilinear polynomial operations
+
+/// Errors returned by the checked polynomial operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Operands have different numbers of variables
+    DimensionMismatch { left: usize, right: usize },
+    /// A combination of zero polynomials has no number of variables
+    EmptyCombination,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::DimensionMismatch { left, right } => write!(
+                f,
+                "dimension mismatch: {} variables vs {} variables",
+                left, right
+            ),
+            Error::EmptyCombination => write!(f, "empty combination: no polynomials given"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Result type for multilinear polynomial operations
+pub type Result<T> = core::result::Result<T, Error>;
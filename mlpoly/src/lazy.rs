@@ -0,0 +1,136 @@
+//! Multilinear polynomials whose evaluations are computed on demand
+//!
+//! `LazyMLPoly` wraps a function `index -> f(index)` (LSB-first indexing, as in
+//! `MLPoly::evals`) and never stores the 2^n evaluations: round sums are
+//! recomputed from the function, and binding x_1 materializes f(r, ·) as a dense
+//! `MLPoly` of half the size.
+
+use ark_ff::Field;
+
+use crate::multilinear::MultilinearPolynomial;
+use crate::MLPoly;
+
+/// Multilinear polynomial given by a function on the hypercube
+#[derive(Clone)]
+pub struct LazyMLPoly<G> {
+    /// Number of variables
+    pub n_vars: usize,
+    /// `eval(i)` is f at the hypercube point with binary representation i
+    pub eval: G,
+}
+
+impl<G> LazyMLPoly<G> {
+    pub fn new(n_vars: usize, eval: G) -> Self {
+        Self { n_vars, eval }
+    }
+
+    /// Compute all 2^n evaluations
+    pub fn to_dense<F: Field>(&self) -> MLPoly<F>
+    where
+        G: Fn(usize) -> F,
+    {
+        MLPoly {
+            n_vars: self.n_vars,
+            evals: (0..1usize << self.n_vars).map(&self.eval).collect(),
+        }
+    }
+}
+
+impl<F: Field, G: Fn(usize) -> F> MultilinearPolynomial<F> for LazyMLPoly<G> {
+    type Folded = MLPoly<F>;
+
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn round_sum_g0_g1(&self) -> (F, F) {
+        let mut sums = [F::ZERO; 2];
+        for i in 0..1usize << self.n_vars {
+            sums[i & 1] += (self.eval)(i);
+        }
+        (sums[0], sums[1])
+    }
+
+    fn fold_first_var(&self, r: F) -> MLPoly<F> {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let evals = (0..1usize << (self.n_vars - 1))
+            .map(|j| {
+                let lo = (self.eval)(2 * j);
+                lo + r * ((self.eval)(2 * j + 1) - lo)
+            })
+            .collect();
+        MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    fn round_sum_last_var(&self) -> (F, F) {
+        assert!(self.n_vars > 0, "a constant polynomial has no round");
+        let half = 1usize << (self.n_vars - 1);
+        let sums = |range: std::ops::Range<usize>| range.map(&self.eval).sum();
+        (sums(0..half), sums(half..2 * half))
+    }
+
+    fn fold_last_var(&self, r: F) -> MLPoly<F> {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let half = 1usize << (self.n_vars - 1);
+        let evals = (0..half)
+            .map(|j| {
+                let lo = (self.eval)(j);
+                lo + r * ((self.eval)(j + half) - lo)
+            })
+            .collect();
+        MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    fn sum_all(&self) -> F {
+        (0..1usize << self.n_vars).map(&self.eval).sum()
+    }
+
+    fn eval_at(&self, x: &[F]) -> F {
+        assert_eq!(
+            x.len(),
+            self.n_vars,
+            "wrong number of evaluation points: given {}, expected {}",
+            x.len(),
+            self.n_vars
+        );
+        match x.split_first() {
+            None => (self.eval)(0),
+            Some((&x_1, rest)) => self.fold_first_var(x_1).eval_at(rest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn test_lazy_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let lazy = LazyMLPoly::new(6, |i: usize| Fr::from((i * i) as u64) - Fr::from(3u64));
+        let dense: MLPoly<Fr> = lazy.to_dense();
+
+        assert_eq!(lazy.sum_all(), dense.sum_all());
+        assert_eq!(
+            MultilinearPolynomial::round_sum_g0_g1(&lazy),
+            dense.round_sum_g0_g1()
+        );
+        let r = Fr::rand(&mut rng);
+        assert_eq!(lazy.fold_first_var(r), dense.fold_first_var(r));
+        assert_eq!(
+            MultilinearPolynomial::round_sum_last_var(&lazy),
+            dense.round_sum_last_var()
+        );
+        assert_eq!(lazy.fold_last_var(r), dense.fold_last_var(r));
+        let x: Vec<Fr> = (0..6).map(|_| Fr::rand(&mut rng)).collect();
+        assert_eq!(lazy.eval_at(&x), dense.eval_at(&x));
+    }
+}
@@ -2,9 +2,82 @@
 //!
 //! A multilinear polynomial over n variables is stored as its evaluations
 //! at all 2^n points of the boolean hypercube {0,1}^n.
+//!
+//! With the `parallel` feature, `sum_all`, `fold_first_var`, `round_sum_g0_g1`
+//! and (through folding) `eval_at` split their loops across a rayon thread pool.
+//! Field addition is exact, so results are identical to the serial build.
+
+pub mod error;
+pub mod lazy;
+pub mod merkle;
+pub mod mmap;
+pub mod multilinear;
+pub mod ops;
+pub mod small;
+pub mod sparse;
+pub mod vars;
 
 use ark_ff::Field;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// `error::Result` stays module-qualified: a `Result` alias here would shadow
+// the std one inside the serialization derives below
+pub use error::Error;
+pub use lazy::LazyMLPoly;
+pub use merkle::{MerklePath, MerkleTree};
+pub use mmap::{MLPolyWriter, MmapMLPoly};
+pub use multilinear::MultilinearPolynomial;
+pub use small::{SmallMLPoly, SmallValue};
+pub use sparse::SparseMLPoly;
+
+/// Evaluate eq(x, y) = ∏_i (x_i y_i + (1 - x_i)(1 - y_i)) in O(n)
+pub fn eq_eval<F: Field>(x: &[F], y: &[F]) -> F {
+    assert_eq!(x.len(), y.len(), "eq_eval: points have different lengths");
+    x.iter()
+        .zip(y)
+        .map(|(&a, &b)| a * b + (F::ONE - a) * (F::ONE - b))
+        .product()
+}
+
+/// Halve `evals` in place on the rayon pool and combine the results of `fold`
+///
+/// Output chunk j (`width` slots) is written by `fold` from input chunk j
+/// (`2 * width` slots), so it only overwrites input chunks below j. Chunk 0 is
+/// folded first from a copy; then each level of chunks [m, 2m) is folded in
+/// parallel, since its inputs lie above every output written so far and its
+/// outputs only cover inputs already consumed. The vector is truncated to the
+/// outputs and keeps its capacity.
+#[cfg(feature = "parallel")]
+fn par_fold_in_place<F: Field, T: Send + Default>(
+    evals: &mut Vec<F>,
+    width: usize,
+    fold: impl Fn(&[F], &mut [F]) -> T + Sync,
+    combine: impl Fn(T, T) -> T + Sync + Send,
+) -> T {
+    assert!(width <= 2, "chunks wider than two slots are not supported");
+    let chunks = evals.len() / (2 * width);
+
+    let mut first = [F::ZERO; 4];
+    first[..2 * width].copy_from_slice(&evals[..2 * width]);
+    let mut acc = fold(&first[..2 * width], &mut evals[..width]);
+
+    let mut m = 1;
+    while m < chunks {
+        let (lo, hi) = evals.split_at_mut(2 * width * m);
+        let level = lo[width * m..]
+            .par_chunks_mut(width)
+            .zip(hi[..2 * width * m].par_chunks(2 * width))
+            .map(|(out, input)| fold(input, out))
+            .reduce(T::default, &combine);
+        acc = combine(acc, level);
+        m *= 2;
+    }
+
+    evals.truncate(width * chunks);
+    acc
+}
 
 /// Multilinear polynomial in evaluation form
 ///
@@ -29,6 +102,27 @@ impl<F: Field> MLPoly<F> {
         }
     }
 
+    /// Evaluation table of eq(r, x) = ∏_i (r_i x_i + (1 - r_i)(1 - x_i)) over {0,1}^n
+    ///
+    /// Built in O(2^n) with the doubling trick: each r_i splits every entry
+    /// `e` of the table for (r_1, ..., r_{i-1}) into `e * (1 - r_i)` and `e * r_i`.
+    pub fn eq_table(r: &[F]) -> Self {
+        let mut evals = Vec::with_capacity(1 << r.len());
+        evals.push(F::ONE);
+        for &r_i in r {
+            let size = evals.len();
+            for j in 0..size {
+                let hi = evals[j] * r_i;
+                evals[j] -= hi;
+                evals.push(hi);
+            }
+        }
+        Self {
+            n_vars: r.len(),
+            evals,
+        }
+    }
+
     /// Create from evaluations, inferring n_vars from length
     ///
     /// # Panics
@@ -66,7 +160,13 @@ impl<F: Field> MLPoly<F> {
 
     /// Sum of all evaluations: ∑_{x ∈ {0,1}^n} f(x)
     pub fn sum_all(&self) -> F {
-        self.evals.iter().sum()
+        #[cfg(feature = "parallel")]
+        let sum = self.evals.par_iter().copied().reduce(|| F::ZERO, |a, b| a + b);
+
+        #[cfg(not(feature = "parallel"))]
+        let sum = self.evals.iter().sum();
+
+        sum
     }
 
     /// Fold the first variable at point r
@@ -78,6 +178,13 @@ impl<F: Field> MLPoly<F> {
         let half = self.len() / 2;
         let one_minus_r = F::ONE - r;
 
+        #[cfg(feature = "parallel")]
+        let evals: Vec<F> = (0..half)
+            .into_par_iter()
+            .map(|i| self.evals[2 * i] * one_minus_r + self.evals[2 * i + 1] * r)
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
         let evals: Vec<F> = (0..half)
             .map(|i| self.evals[2 * i] * one_minus_r + self.evals[2 * i + 1] * r)
             .collect();
@@ -88,6 +195,117 @@ impl<F: Field> MLPoly<F> {
         }
     }
 
+    /// Fold the first variable at point r, reusing the evaluation buffer
+    ///
+    /// Same result as `fold_first_var`, but f(r, x_2, ..., x_n) is written into the
+    /// lower half of `evals`, which is then truncated; no memory is allocated and
+    /// the capacity is kept for the remaining rounds.
+    pub fn fold_first_var_in_place(&mut self, r: F) {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+
+        // Fold pair i into slot i, level by level so no input is overwritten early
+        #[cfg(feature = "parallel")]
+        par_fold_in_place(
+            &mut self.evals,
+            1,
+            |pair, out| out[0] = pair[0] + r * (pair[1] - pair[0]),
+            |_, _| (),
+        );
+
+        // Slot i only reads slots 2i and 2i + 1, which have not been overwritten yet
+        #[cfg(not(feature = "parallel"))]
+        {
+            let half = self.len() / 2;
+            for i in 0..half {
+                let (lo, hi) = (self.evals[2 * i], self.evals[2 * i + 1]);
+                self.evals[i] = lo + r * (hi - lo);
+            }
+            self.evals.truncate(half);
+        }
+
+        self.n_vars -= 1;
+    }
+
+    /// Fold the first variable at r in place and return the next round's (g(0), g(1))
+    ///
+    /// Equivalent to `fold_first_var_in_place(r)` followed by `round_sum_g0_g1()`,
+    /// but each group of four evaluations is read once: it folds into the pair
+    /// (f(r, 0, y), f(r, 1, y)), which is written back and added to the sums.
+    ///
+    /// # Panics
+    /// Panics if fewer than two variables are left
+    pub fn fold_and_round_sum(&mut self, r: F) -> (F, F) {
+        assert!(
+            self.n_vars >= 2,
+            "need two variables to fold and compute the next round"
+        );
+        let quarter = self.len() / 4;
+
+        // Fold group i into slots 2i and 2i + 1, level by level so no input is
+        // overwritten early
+        #[cfg(feature = "parallel")]
+        let sums = par_fold_in_place(
+            &mut self.evals,
+            2,
+            |quad, out| {
+                out[0] = quad[0] + r * (quad[1] - quad[0]);
+                out[1] = quad[2] + r * (quad[3] - quad[2]);
+                (out[0], out[1])
+            },
+            |a, b| (a.0 + b.0, a.1 + b.1),
+        );
+
+        // Slots 2i and 2i + 1 only read slots 4i, ..., 4i + 3, which are still intact
+        #[cfg(not(feature = "parallel"))]
+        let sums = {
+            let mut g0 = F::ZERO;
+            let mut g1 = F::ZERO;
+            for i in 0..quarter {
+                let lo = self.evals[4 * i] + r * (self.evals[4 * i + 1] - self.evals[4 * i]);
+                let hi = self.evals[4 * i + 2] + r * (self.evals[4 * i + 3] - self.evals[4 * i + 2]);
+                self.evals[2 * i] = lo;
+                self.evals[2 * i + 1] = hi;
+                g0 += lo;
+                g1 += hi;
+            }
+            (g0, g1)
+        };
+
+        self.evals.truncate(2 * quarter);
+        self.n_vars -= 1;
+        sums
+    }
+
+    /// Fold the first variable at a point r of an extension field E ⊇ F
+    ///
+    /// Returns f(r, x_2, ..., x_n) over E. Sumchecks over a small base field use
+    /// this to leave the base field after round 1, once the challenge is in E.
+    pub fn fold_first_var_ext<E: Field<BasePrimeField = F>>(&self, r: E) -> MLPoly<E> {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let fold = |pair: &[F]| {
+            E::from_base_prime_field(pair[0]) + r * E::from_base_prime_field(pair[1] - pair[0])
+        };
+
+        #[cfg(feature = "parallel")]
+        let evals: Vec<E> = self.evals.par_chunks(2).map(fold).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let evals: Vec<E> = self.evals.chunks(2).map(fold).collect();
+
+        MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    /// Embed every evaluation into an extension field E ⊇ F
+    pub fn to_ext<E: Field<BasePrimeField = F>>(&self) -> MLPoly<E> {
+        MLPoly {
+            n_vars: self.n_vars,
+            evals: self.evals.iter().map(|&e| E::from_base_prime_field(e)).collect(),
+        }
+    }
+
     /// Fold multiple variables sequentially
     ///
     /// `fold_many(&[r_1, r_2, ..., r_k])` returns f(r_1, r_2, ..., r_k, x_{k+1}, ..., x_n)
@@ -117,20 +335,39 @@ impl<F: Field> MLPoly<F> {
         self.fold_many(x).evals[0]
     }
 
+    /// Commit to the evaluations with a Blake2s Merkle tree
+    ///
+    /// Leaf i is `evals[i]`, so `open(i)` authenticates f at the hypercube point
+    /// whose binary representation is i.
+    pub fn commit_merkle(&self) -> MerkleTree {
+        MerkleTree::from_leaves(&self.evals)
+    }
+
     /// Compute g(0) and g(1) for the round polynomial in sumcheck
     ///
     /// Returns (g(0), g(1)) where:
     /// - g(0) = ∑_{x_2,...,x_n} f(0, x_2, ..., x_n)
     /// - g(1) = ∑_{x_2,...,x_n} f(1, x_2, ..., x_n)
     pub fn round_sum_g0_g1(&self) -> (F, F) {
-        let half = self.len() / 2;
-        let mut g0 = F::ZERO;
-        let mut g1 = F::ZERO;
-        for j in 0..half {
-            g0 += self.evals[2 * j];
-            g1 += self.evals[2 * j + 1];
+        #[cfg(feature = "parallel")]
+        {
+            self.evals
+                .par_chunks_exact(2)
+                .map(|pair| (pair[0], pair[1]))
+                .reduce(|| (F::ZERO, F::ZERO), |a, b| (a.0 + b.0, a.1 + b.1))
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            let half = self.len() / 2;
+            let mut g0 = F::ZERO;
+            let mut g1 = F::ZERO;
+            for j in 0..half {
+                g0 += self.evals[2 * j];
+                g1 += self.evals[2 * j + 1];
+            }
+            (g0, g1)
         }
-        (g0, g1)
     }
 }
 
@@ -178,6 +415,53 @@ mod tests {
         assert_eq!(folded.evals, vec![Fr::from(2u64), Fr::from(4u64)]);
     }
 
+    #[test]
+    fn test_fold_first_var_in_place() {
+        let mut rng = ark_std::test_rng();
+        let evals: Vec<Fr> = (0..(1 << 6)).map(|_| Fr::rand(&mut rng)).collect();
+        let mut poly = MLPoly::from_evals(evals);
+        let capacity = poly.evals.capacity();
+
+        for _ in 0..6 {
+            let r = Fr::rand(&mut rng);
+            let expected = poly.fold_first_var(r);
+            poly.fold_first_var_in_place(r);
+            assert_eq!(poly, expected);
+        }
+        assert!(poly.is_constant());
+        assert_eq!(poly.evals.capacity(), capacity, "buffer should be reused");
+    }
+
+    #[test]
+    fn test_fold_and_round_sum() {
+        let mut rng = ark_std::test_rng();
+        let evals: Vec<Fr> = (0..(1 << 7)).map(|_| Fr::rand(&mut rng)).collect();
+        let mut fused = MLPoly::from_evals(evals);
+        let mut two_pass = fused.clone();
+        let capacity = fused.evals.capacity();
+
+        while fused.n_vars >= 2 {
+            let r = Fr::rand(&mut rng);
+            let sums = fused.fold_and_round_sum(r);
+            two_pass.fold_first_var_in_place(r);
+            assert_eq!(fused, two_pass);
+            assert_eq!(sums, two_pass.round_sum_g0_g1());
+        }
+        assert_eq!(fused.evals.capacity(), capacity, "buffer should be reused");
+    }
+
+    #[test]
+    fn test_fold_first_var_ext() {
+        use ark_bn254::{Fq, Fq2};
+
+        let mut rng = ark_std::test_rng();
+        let poly = MLPoly::from_evals((0..16).map(|_| Fq::rand(&mut rng)).collect());
+        let r = Fq2::rand(&mut rng);
+
+        let folded: MLPoly<Fq2> = poly.fold_first_var_ext(r);
+        assert_eq!(folded, poly.to_ext::<Fq2>().fold_first_var(r));
+    }
+
     #[test]
     fn test_eval_at() {
         // f(x_1, x_2) = 1 + x_1 + 2*x_2 + x_1*x_2
@@ -204,6 +488,51 @@ mod tests {
         assert_eq!(g0 + g1, poly.sum_all());
     }
 
+    #[test]
+    fn test_matches_serial_reference() {
+        // Checked in both builds; with `parallel` this pins the rayon paths to the loops
+        let mut rng = ark_std::test_rng();
+        for n_vars in [0, 1, 11] {
+            let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+            let poly = MLPoly::from_evals(evals.clone());
+            let r = Fr::rand(&mut rng);
+
+            let sum: Fr = evals.iter().sum();
+            let (mut g0, mut g1) = (Fr::from(0u64), Fr::from(0u64));
+            let mut folded = Vec::with_capacity(evals.len() / 2);
+            for pair in evals.chunks_exact(2) {
+                g0 += pair[0];
+                g1 += pair[1];
+                folded.push(pair[0] + r * (pair[1] - pair[0]));
+            }
+
+            assert_eq!(poly.sum_all(), sum);
+            assert_eq!(poly.round_sum_g0_g1(), (g0, g1));
+            if n_vars > 0 {
+                assert_eq!(poly.fold_first_var(r).evals, folded);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eq_table() {
+        let mut rng = ark_std::test_rng();
+        let r: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        let table = MLPoly::eq_table(&r);
+        assert_eq!(table.n_vars, 3);
+
+        // Entry i is eq(r, bits of i) with x_1 as the least significant bit
+        for (i, e) in table.evals.iter().enumerate() {
+            let bits: Vec<Fr> = (0..3).map(|k| Fr::from(((i >> k) & 1) as u64)).collect();
+            assert_eq!(*e, eq_eval(&r, &bits));
+        }
+
+        // The MLE of the table is eq(r, ·) itself
+        let x: Vec<Fr> = (0..3).map(|_| Fr::rand(&mut rng)).collect();
+        assert_eq!(table.eval_at(&x), eq_eval(&r, &x));
+        assert_eq!(table.sum_all(), Fr::from(1u64));
+    }
+
     #[test]
     fn test_serialization_roundtrip() {
         use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -0,0 +1,195 @@
+//! Merkle commitment to the evaluation vector of an `MLPoly`
+//!
+//! Leaves are the evaluations in hypercube order (index i holds f(bits of i),
+//! x_1 least significant), hashed with Blake2s. Leaf and inner-node hashes use
+//! distinct prefixes so that a node can never be passed off as a leaf.
+
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use blake2::{Blake2s256, Digest as _};
+
+/// Output of the tree hash
+pub type Digest = [u8; 32];
+
+fn hash_leaf<F: Field>(value: &F) -> Digest {
+    let mut buf = Vec::new();
+    value.serialize_compressed(&mut buf).expect("serialize");
+    let mut h = Blake2s256::new();
+    h.update([0u8]);
+    h.update(&buf);
+    h.finalize().into()
+}
+
+fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut h = Blake2s256::new();
+    h.update([1u8]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// Binary Merkle tree over a power-of-two number of field elements
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// `layers[0]` are the leaf hashes, the last layer is `[root]`
+    layers: Vec<Vec<Digest>>,
+}
+
+/// Authentication path for a single leaf
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MerklePath {
+    /// Hypercube index of the opened leaf
+    pub index: usize,
+    /// Sibling hashes from the leaf level up to just below the root
+    pub siblings: Vec<Digest>,
+}
+
+impl MerkleTree {
+    /// Build the tree over `leaves`
+    ///
+    /// # Panics
+    /// Panics if leaves.len() is not a power of 2
+    pub fn from_leaves<F: Field>(leaves: &[F]) -> Self {
+        assert!(
+            leaves.len().is_power_of_two(),
+            "leaves length must be a power of 2"
+        );
+        let mut layers = vec![leaves.iter().map(hash_leaf).collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_node(&pair[0], &pair[1]))
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// The commitment
+    pub fn root(&self) -> Digest {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Number of levels above the leaves (= n_vars of the committed polynomial)
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Authentication path for the leaf at `index`
+    pub fn open(&self, index: usize) -> MerklePath {
+        assert!(index < self.layers[0].len(), "leaf index out of range");
+        let siblings = self.layers[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1])
+            .collect();
+        MerklePath { index, siblings }
+    }
+}
+
+impl MerklePath {
+    /// Check that `leaf` sits at `self.index` in the tree with the given root
+    ///
+    /// `depth` is the tree depth the verifier expects (the committed
+    /// polynomial's n_vars); a path of any other length is rejected.
+    pub fn verify<F: Field>(&self, root: &Digest, depth: usize, leaf: &F) -> bool {
+        if self.siblings.len() != depth || self.index.checked_shr(depth as u32) != Some(0) {
+            return false;
+        }
+        let mut cur = hash_leaf(leaf);
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            cur = if (self.index >> level) & 1 == 0 {
+                hash_node(&cur, sibling)
+            } else {
+                hash_node(sibling, &cur)
+            };
+        }
+        &cur == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MLPoly;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        MLPoly::from_evals((0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect())
+    }
+
+    #[test]
+    fn test_open_verify_every_index() {
+        let poly = random_poly(4);
+        let tree = poly.commit_merkle();
+        let root = tree.root();
+        assert_eq!(tree.depth(), 4);
+
+        // The verifier only sees the root, the path and the claimed leaf
+        for (i, e) in poly.evals.iter().enumerate() {
+            let path = tree.open(i);
+            assert_eq!(path.siblings.len(), 4);
+            assert!(path.verify(&root, 4, e));
+        }
+    }
+
+    #[test]
+    fn test_tampered_opening_fails() {
+        let poly = random_poly(3);
+        let tree = poly.commit_merkle();
+        let root = tree.root();
+
+        let mut path = tree.open(5);
+        assert!(!path.verify(&root, 3, &(poly.evals[5] + Fr::from(1u64))));
+        assert!(!path.verify(&root, 3, &poly.evals[4]));
+
+        path.index = 4;
+        assert!(!path.verify(&root, 3, &poly.evals[5]));
+        path.index = 5 + 8;
+        assert!(!path.verify(&root, 3, &poly.evals[5]));
+    }
+
+    #[test]
+    fn test_wrong_length_path_fails() {
+        let poly = random_poly(3);
+        let tree = poly.commit_merkle();
+        let root = tree.root();
+        let path = tree.open(5);
+        assert!(!path.verify(&root, 2, &poly.evals[5]));
+
+        // An oversized path from an untrusted proof must not overflow the index shift
+        let mut long = path.clone();
+        long.siblings.resize(70, [0u8; 32]);
+        assert!(!long.verify(&root, 3, &poly.evals[5]));
+        assert!(!long.verify(&root, 70, &poly.evals[5]));
+    }
+
+    #[test]
+    fn test_root_binds_evaluations() {
+        let poly = random_poly(3);
+        let mut other = poly.clone();
+        other.evals[2] += Fr::from(1u64);
+        assert_ne!(poly.commit_merkle().root(), other.commit_merkle().root());
+
+        // Single-evaluation polynomial: the root is the leaf hash
+        let constant = MLPoly::from_evals(vec![Fr::from(7u64)]);
+        let tree = constant.commit_merkle();
+        assert_eq!(tree.depth(), 0);
+        assert!(tree.open(0).verify(&tree.root(), 0, &Fr::from(7u64)));
+    }
+
+    #[test]
+    fn test_path_serialization_roundtrip() {
+        let tree = random_poly(3).commit_merkle();
+        let path = tree.open(6);
+
+        let mut bytes = Vec::new();
+        path.serialize_compressed(&mut bytes).unwrap();
+        let path2 = MerklePath::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(path, path2);
+    }
+}
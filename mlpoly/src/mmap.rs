@@ -0,0 +1,354 @@
+//! File-backed evaluation vectors, opened read-only with mmap
+//!
+//! Layout (all integers little-endian):
+//!
+//! | offset | size | field                                   |
+//! |--------|------|-----------------------------------------|
+//! | 0      | 8    | magic `b"MLPOLY\0\0"`                   |
+//! | 8      | 4    | format version (`FORMAT_VERSION`)       |
+//! | 12     | 4    | element size in bytes                   |
+//! | 16     | 8    | n_vars                                  |
+//! | 24     | 8    | reserved, zero                          |
+//! | 32     | ...  | 2^n elements, `serialize_uncompressed`  |
+//!
+//! Every element takes the same number of bytes: the little-endian limbs of its
+//! canonical representative (of each coefficient, for extension fields). f at
+//! index i is the slice at `HEADER_LEN + i * elem_size`, so the bytes of any
+//! element are read straight from the mapping without parsing the rest of the
+//! file, and the 32-byte header keeps them 8-byte aligned.
+//!
+//! The view does not hand out `&F` borrowed from the mapping: arkworks keeps
+//! field elements in Montgomery form, so each access converts the limbs (a range
+//! check and one multiplication). That conversion is also where a corrupt
+//! element is detected; `open` only checks the header and the file length, and
+//! the reading methods return `InvalidData` when they reach a bad element.
+//! Binding the first variable produces an ordinary `MLPoly` of half the size;
+//! later rounds run in memory.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use ark_ff::Field;
+use memmap2::Mmap;
+
+use crate::MLPoly;
+
+/// File magic
+pub const MAGIC: &[u8; 8] = b"MLPOLY\0\0";
+/// Current layout version
+pub const FORMAT_VERSION: u32 = 1;
+/// Size of the fixed header in bytes
+pub const HEADER_LEN: usize = 32;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn elem_size<F: Field>() -> usize {
+    F::ZERO.uncompressed_size()
+}
+
+fn decode<F: Field>(bytes: &[u8]) -> io::Result<F> {
+    F::deserialize_uncompressed(bytes).map_err(|_| invalid("corrupt evaluation"))
+}
+
+/// Writes evaluations to disk one at a time, without holding them in memory
+pub struct MLPolyWriter<F: Field> {
+    out: BufWriter<File>,
+    n_vars: usize,
+    written: usize,
+    buf: Vec<u8>,
+    _field: PhantomData<F>,
+}
+
+impl<F: Field> MLPolyWriter<F> {
+    /// Create (or truncate) `path` and write the header for `n_vars` variables
+    pub fn create<P: AsRef<Path>>(path: P, n_vars: usize) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(MAGIC)?;
+        out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        out.write_all(&(elem_size::<F>() as u32).to_le_bytes())?;
+        out.write_all(&(n_vars as u64).to_le_bytes())?;
+        out.write_all(&0u64.to_le_bytes())?;
+        Ok(Self {
+            out,
+            n_vars,
+            written: 0,
+            buf: Vec::with_capacity(elem_size::<F>()),
+            _field: PhantomData,
+        })
+    }
+
+    /// Append the next evaluation
+    pub fn push(&mut self, value: &F) -> io::Result<()> {
+        if self.written == 1 << self.n_vars {
+            return Err(invalid("more evaluations than 2^n_vars"));
+        }
+        self.buf.clear();
+        value
+            .serialize_uncompressed(&mut self.buf)
+            .map_err(|_| invalid("failed to serialize evaluation"))?;
+        self.out.write_all(&self.buf)?;
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Flush the file; fails if fewer than 2^n_vars evaluations were pushed
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.written != 1 << self.n_vars {
+            return Err(invalid("fewer evaluations than 2^n_vars"));
+        }
+        self.out.flush()
+    }
+}
+
+impl<F: Field> MLPoly<F> {
+    /// Write the evaluations to `path` in the fixed on-disk layout
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = MLPolyWriter::create(path, self.n_vars)?;
+        for e in &self.evals {
+            writer.push(e)?;
+        }
+        writer.finish()
+    }
+}
+
+/// Read-only view of an evaluation file written by `MLPolyWriter`
+pub struct MmapMLPoly<F: Field> {
+    pub n_vars: usize,
+    map: Mmap,
+    elem_size: usize,
+    _field: PhantomData<F>,
+}
+
+impl<F: Field> MmapMLPoly<F> {
+    /// Map `path` and check its header and length against `F`
+    ///
+    /// Elements are not read here; each one is validated when it is decoded. The
+    /// file must not be modified while the view is alive.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; callers must not modify the file while it is mapped
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() < HEADER_LEN || &map[..8] != MAGIC {
+            return Err(invalid("not an MLPoly file"));
+        }
+        let word = |at: usize| u32::from_le_bytes(map[at..at + 4].try_into().unwrap());
+        if word(8) != FORMAT_VERSION {
+            return Err(invalid("unsupported format version"));
+        }
+        let size = word(12) as usize;
+        if size != elem_size::<F>() {
+            return Err(invalid("element size does not match the field"));
+        }
+        let n_vars = u64::from_le_bytes(map[16..24].try_into().unwrap()) as usize;
+        let expected = Some(n_vars)
+            .filter(|&n| n < usize::BITS as usize)
+            .and_then(|n| (1usize << n).checked_mul(size))
+            .and_then(|body| body.checked_add(HEADER_LEN));
+        if expected != Some(map.len()) {
+            return Err(invalid("file length does not match n_vars"));
+        }
+
+        Ok(Self {
+            n_vars,
+            map,
+            elem_size: size,
+            _field: PhantomData,
+        })
+    }
+
+    /// Number of evaluations: 2^n_vars
+    #[inline]
+    pub fn len(&self) -> usize {
+        1 << self.n_vars
+    }
+
+    /// Always false: even a constant has one evaluation
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Evaluation at index i, decoded from the mapping
+    ///
+    /// Returns `Ok(None)` past the end and `InvalidData` if the element is corrupt.
+    pub fn get(&self, index: usize) -> io::Result<Option<F>> {
+        if index >= self.len() {
+            return Ok(None);
+        }
+        let start = HEADER_LEN + index * self.elem_size;
+        decode(&self.map[start..start + self.elem_size]).map(Some)
+    }
+
+    /// All evaluations in index order, decoded one at a time
+    pub fn iter(&self) -> impl Iterator<Item = io::Result<F>> + '_ {
+        self.map[HEADER_LEN..]
+            .chunks_exact(self.elem_size)
+            .map(decode)
+    }
+
+    /// Sum of all evaluations: ∑_{x ∈ {0,1}^n} f(x)
+    pub fn sum_all(&self) -> io::Result<F> {
+        self.iter().sum()
+    }
+
+    /// (g(0), g(1)) of the first sumcheck round, as in `MLPoly::round_sum_g0_g1`
+    pub fn round_sum_g0_g1(&self) -> io::Result<(F, F)> {
+        let mut sums = [F::ZERO; 2];
+        for (i, e) in self.iter().enumerate() {
+            sums[i & 1] += e?;
+        }
+        Ok((sums[0], sums[1]))
+    }
+
+    /// Fold the first variable at point r into an in-memory polynomial
+    ///
+    /// # Panics
+    /// Panics if the polynomial is constant
+    pub fn fold_first_var(&self, r: F) -> io::Result<MLPoly<F>> {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let mut evals = Vec::with_capacity(self.len() / 2);
+        let mut it = self.iter();
+        while let (Some(lo), Some(hi)) = (it.next(), it.next()) {
+            let (lo, hi) = (lo?, hi?);
+            evals.push(lo + r * (hi - lo));
+        }
+        Ok(MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        })
+    }
+
+    /// Fold the first variable at r and return the folded polynomial with its next
+    /// round's (g(0), g(1))
+    ///
+    /// One pass over the mapping, like `MLPoly::fold_and_round_sum`: each group of
+    /// four evaluations folds into (f(r, 0, y), f(r, 1, y)), which is stored and
+    /// added to the sums.
+    ///
+    /// # Panics
+    /// Panics if fewer than two variables are left
+    pub fn fold_and_round_sum(&self, r: F) -> io::Result<(MLPoly<F>, (F, F))> {
+        assert!(
+            self.n_vars >= 2,
+            "need two variables to fold and compute the next round"
+        );
+        let mut evals = Vec::with_capacity(self.len() / 2);
+        let mut sums = [F::ZERO; 2];
+        let mut it = self.iter();
+        while let (Some(lo), Some(hi)) = (it.next(), it.next()) {
+            let (lo, hi) = (lo?, hi?);
+            let folded = lo + r * (hi - lo);
+            sums[evals.len() & 1] += folded;
+            evals.push(folded);
+        }
+        let folded = MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        };
+        Ok((folded, (sums[0], sums[1])))
+    }
+
+    /// Load every evaluation into memory
+    pub fn to_mlpoly(&self) -> io::Result<MLPoly<F>> {
+        Ok(MLPoly {
+            n_vars: self.n_vars,
+            evals: self.iter().collect::<io::Result<_>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("mlpoly-{}-{}.bin", name, std::process::id()))
+    }
+
+    fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        MLPoly::from_evals((0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect())
+    }
+
+    #[test]
+    fn test_roundtrip_and_fold() {
+        let path = temp_path("roundtrip");
+        let poly = random_poly(6);
+        poly.write_to_file(&path).unwrap();
+
+        let view = MmapMLPoly::<Fr>::open(&path).unwrap();
+        assert_eq!(view.n_vars, 6);
+        assert_eq!(view.get(5).unwrap(), Some(poly.evals[5]));
+        assert_eq!(view.get(64).unwrap(), None);
+        assert_eq!(view.to_mlpoly().unwrap(), poly);
+        assert_eq!(view.sum_all().unwrap(), poly.sum_all());
+        assert_eq!(view.round_sum_g0_g1().unwrap(), poly.round_sum_g0_g1());
+
+        let r = Fr::from(17u64);
+        assert_eq!(view.fold_first_var(r).unwrap(), poly.fold_first_var(r));
+
+        let mut expected = poly.clone();
+        let sums = expected.fold_and_round_sum(r);
+        assert_eq!(view.fold_and_round_sum(r).unwrap(), (expected, sums));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_writer_enforces_length() {
+        let path = temp_path("writer");
+        let mut writer = MLPolyWriter::<Fr>::create(&path, 1).unwrap();
+        writer.push(&Fr::from(1u64)).unwrap();
+        assert!(writer.finish().is_err());
+
+        let mut writer = MLPolyWriter::<Fr>::create(&path, 1).unwrap();
+        writer.push(&Fr::from(1u64)).unwrap();
+        writer.push(&Fr::from(2u64)).unwrap();
+        assert!(writer.push(&Fr::from(3u64)).is_err());
+        writer.finish().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_bad_files() {
+        let path = temp_path("bad");
+        random_poly(3).write_to_file(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        // Truncated body
+        std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(MmapMLPoly::<Fr>::open(&path).is_err());
+
+        // Wrong magic
+        let mut bad = bytes.clone();
+        bad[0] ^= 1;
+        std::fs::write(&path, &bad).unwrap();
+        assert!(MmapMLPoly::<Fr>::open(&path).is_err());
+
+        // Wrong element size
+        let mut bad = bytes.clone();
+        bad[12] = 8;
+        std::fs::write(&path, &bad).unwrap();
+        assert!(MmapMLPoly::<Fr>::open(&path).is_err());
+
+        // An element that is not a canonical field encoding: the header is fine,
+        // the error surfaces when the element is read
+        let mut bad = bytes;
+        let size = elem_size::<Fr>();
+        bad[HEADER_LEN + 5 * size..HEADER_LEN + 6 * size].fill(0xff);
+        std::fs::write(&path, &bad).unwrap();
+        let view = MmapMLPoly::<Fr>::open(&path).unwrap();
+        assert!(view.get(4).unwrap().is_some());
+        assert!(view.get(5).is_err());
+        assert!(view.sum_all().is_err());
+        assert!(view.fold_first_var(Fr::from(3u64)).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
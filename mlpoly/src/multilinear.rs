@@ -0,0 +1,149 @@
+//! Operations shared by every representation of a multilinear polynomial
+//!
+//! The sumcheck prover only needs to compute the round sums, bind the first
+//! (or, for high-to-low binding, the last) variable and read off the final
+//! constant; `MultilinearPolynomial` captures
+//! exactly that so provers can run on dense, sparse, lazily computed or
+//! third-party representations. A new representation implements the seven
+//! required methods; the in-place methods have allocating defaults that
+//! storage-owning types can override.
+
+use ark_ff::Field;
+
+use crate::MLPoly;
+
+/// A multilinear polynomial f(x_1, ..., x_n) over F
+pub trait MultilinearPolynomial<F: Field> {
+    /// Representation after binding x_1 or x_n; folding it again keeps that type
+    type Folded: MultilinearPolynomial<F, Folded = Self::Folded>;
+
+    /// Number of variables n
+    fn n_vars(&self) -> usize;
+
+    /// (g(0), g(1)) with g(t) = ∑_{x_2, ..., x_n} f(t, x_2, ..., x_n)
+    fn round_sum_g0_g1(&self) -> (F, F);
+
+    /// f(r, x_2, ..., x_n)
+    ///
+    /// # Panics
+    /// Panics if the polynomial is constant
+    fn fold_first_var(&self, r: F) -> Self::Folded;
+
+    /// (g(0), g(1)) with g(t) = ∑_{x_1, ..., x_{n-1}} f(x_1, ..., x_{n-1}, t)
+    ///
+    /// # Panics
+    /// Panics if the polynomial is constant
+    fn round_sum_last_var(&self) -> (F, F);
+
+    /// f(x_1, ..., x_{n-1}, r)
+    ///
+    /// # Panics
+    /// Panics if the polynomial is constant
+    fn fold_last_var(&self, r: F) -> Self::Folded;
+
+    /// ∑_{x ∈ {0,1}^n} f(x)
+    fn sum_all(&self) -> F;
+
+    /// f(x) for x ∈ F^n
+    ///
+    /// # Panics
+    /// Panics if x.len() != n
+    fn eval_at(&self, x: &[F]) -> F;
+
+    /// Replace f by f(r, x_2, ..., x_n)
+    fn fold_first_var_in_place(&mut self, r: F)
+    where
+        Self: MultilinearPolynomial<F, Folded = Self> + Sized,
+    {
+        *self = self.fold_first_var(r);
+    }
+
+    /// Replace f by f(r, x_2, ..., x_n) and return the folded polynomial's (g(0), g(1))
+    ///
+    /// # Panics
+    /// Panics if fewer than two variables are left
+    fn fold_and_round_sum(&mut self, r: F) -> (F, F)
+    where
+        Self: MultilinearPolynomial<F, Folded = Self> + Sized,
+    {
+        assert!(
+            self.n_vars() >= 2,
+            "need two variables to fold and compute the next round"
+        );
+        self.fold_first_var_in_place(r);
+        self.round_sum_g0_g1()
+    }
+
+    /// Replace f by f(x_1, ..., x_{n-1}, r)
+    fn fold_last_var_in_place(&mut self, r: F)
+    where
+        Self: MultilinearPolynomial<F, Folded = Self> + Sized,
+    {
+        *self = self.fold_last_var(r);
+    }
+
+    /// Replace f by f(x_1, ..., x_{n-1}, r) and return the folded polynomial's
+    /// `round_sum_last_var`
+    ///
+    /// # Panics
+    /// Panics if fewer than two variables are left
+    fn fold_last_and_round_sum(&mut self, r: F) -> (F, F)
+    where
+        Self: MultilinearPolynomial<F, Folded = Self> + Sized,
+    {
+        assert!(
+            self.n_vars() >= 2,
+            "need two variables to fold and compute the next round"
+        );
+        self.fold_last_var_in_place(r);
+        self.round_sum_last_var()
+    }
+}
+
+impl<F: Field> MultilinearPolynomial<F> for MLPoly<F> {
+    type Folded = MLPoly<F>;
+
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn round_sum_g0_g1(&self) -> (F, F) {
+        MLPoly::round_sum_g0_g1(self)
+    }
+
+    fn fold_first_var(&self, r: F) -> MLPoly<F> {
+        MLPoly::fold_first_var(self, r)
+    }
+
+    fn round_sum_last_var(&self) -> (F, F) {
+        MLPoly::round_sum_last_var(self)
+    }
+
+    fn fold_last_var(&self, r: F) -> MLPoly<F> {
+        MLPoly::fold_last_var(self, r)
+    }
+
+    fn sum_all(&self) -> F {
+        MLPoly::sum_all(self)
+    }
+
+    fn eval_at(&self, x: &[F]) -> F {
+        MLPoly::eval_at(self, x)
+    }
+
+    fn fold_first_var_in_place(&mut self, r: F) {
+        MLPoly::fold_first_var_in_place(self, r)
+    }
+
+    fn fold_and_round_sum(&mut self, r: F) -> (F, F) {
+        MLPoly::fold_and_round_sum(self, r)
+    }
+
+    fn fold_last_var_in_place(&mut self, r: F) {
+        MLPoly::fold_last_var_in_place(self, r)
+    }
+
+    fn fold_last_and_round_sum(&mut self, r: F) -> (F, F) {
+        MLPoly::fold_last_and_round_sum(self, r)
+    }
+}
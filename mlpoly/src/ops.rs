@@ -0,0 +1,291 @@
+//! Arithmetic on multilinear polynomials in evaluation form
+//!
+//! Sums, differences and scalar multiples of multilinear polynomials are
+//! multilinear, so they act pointwise on the evaluation tables. `+` and `-`
+//! return a `Result`, which is an `Error` when the operands have different
+//! numbers of variables. `+=` and `-=` cannot report failure and panic on such
+//! operands; `try_add_assign` / `try_sub_assign` are their checked versions.
+//! Negation and scalar multiplication cannot fail and return polynomials.
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use ark_ff::Field;
+
+use crate::error::{Error, Result};
+use crate::MLPoly;
+
+fn check_dims<F: Field>(a: &MLPoly<F>, b: &MLPoly<F>) -> Result<()> {
+    if a.n_vars != b.n_vars {
+        return Err(Error::DimensionMismatch {
+            left: a.n_vars,
+            right: b.n_vars,
+        });
+    }
+    Ok(())
+}
+
+impl<F: Field> MLPoly<F> {
+    /// f + g, or an error if the numbers of variables differ
+    pub fn try_add(&self, other: &Self) -> Result<Self> {
+        let mut out = self.clone();
+        out.try_add_assign(other)?;
+        Ok(out)
+    }
+
+    /// f - g, or an error if the numbers of variables differ
+    pub fn try_sub(&self, other: &Self) -> Result<Self> {
+        let mut out = self.clone();
+        out.try_sub_assign(other)?;
+        Ok(out)
+    }
+
+    /// f += g, or an error (leaving f unchanged) if the numbers of variables differ
+    pub fn try_add_assign(&mut self, other: &Self) -> Result<()> {
+        check_dims(self, other)?;
+        for (a, b) in self.evals.iter_mut().zip(&other.evals) {
+            *a += b;
+        }
+        Ok(())
+    }
+
+    /// f -= g, or an error (leaving f unchanged) if the numbers of variables differ
+    pub fn try_sub_assign(&mut self, other: &Self) -> Result<()> {
+        check_dims(self, other)?;
+        for (a, b) in self.evals.iter_mut().zip(&other.evals) {
+            *a -= b;
+        }
+        Ok(())
+    }
+
+    /// f += c · g, or an error (leaving f unchanged) if the numbers of variables differ
+    pub fn try_add_scaled(&mut self, c: F, other: &Self) -> Result<()> {
+        check_dims(self, other)?;
+        for (a, b) in self.evals.iter_mut().zip(&other.evals) {
+            *a += c * b;
+        }
+        Ok(())
+    }
+
+    /// ∑_k c_k · f_k for the given (c_k, f_k) pairs
+    ///
+    /// # Returns
+    /// * `Err(EmptyCombination)` if `terms` is empty
+    /// * `Err(DimensionMismatch)` if the polynomials have different numbers of variables
+    pub fn linear_combination(terms: &[(F, &Self)]) -> Result<Self> {
+        let ((c_0, f_0), rest) = terms.split_first().ok_or(Error::EmptyCombination)?;
+        for (_, f) in rest {
+            check_dims(f_0, f)?;
+        }
+        let mut out = MLPoly {
+            n_vars: f_0.n_vars,
+            evals: f_0.evals.iter().map(|&v| *c_0 * v).collect(),
+        };
+        for (c, f) in rest {
+            out.try_add_scaled(*c, f)?;
+        }
+        Ok(out)
+    }
+
+    /// Pointwise product f(x) · g(x) on every x ∈ {0,1}^n, in the usual index order
+    ///
+    /// The product of two multilinear polynomials has degree 2 in each variable,
+    /// so these are evaluations of f · g on the hypercube only; wrapping them in
+    /// `MLPoly::from_evals` gives the multilinear extension of the table, which
+    /// agrees with f · g on {0,1}^n but not elsewhere.
+    pub fn hadamard(&self, other: &Self) -> Result<Vec<F>> {
+        check_dims(self, other)?;
+        Ok(self
+            .evals
+            .iter()
+            .zip(&other.evals)
+            .map(|(&a, &b)| a * b)
+            .collect())
+    }
+}
+
+impl<F: Field> Add<&MLPoly<F>> for MLPoly<F> {
+    type Output = Result<MLPoly<F>>;
+
+    fn add(mut self, rhs: &MLPoly<F>) -> Result<MLPoly<F>> {
+        self.try_add_assign(rhs)?;
+        Ok(self)
+    }
+}
+
+impl<F: Field> Add<&MLPoly<F>> for &MLPoly<F> {
+    type Output = Result<MLPoly<F>>;
+
+    fn add(self, rhs: &MLPoly<F>) -> Result<MLPoly<F>> {
+        self.try_add(rhs)
+    }
+}
+
+impl<F: Field> Sub<&MLPoly<F>> for MLPoly<F> {
+    type Output = Result<MLPoly<F>>;
+
+    fn sub(mut self, rhs: &MLPoly<F>) -> Result<MLPoly<F>> {
+        self.try_sub_assign(rhs)?;
+        Ok(self)
+    }
+}
+
+impl<F: Field> Sub<&MLPoly<F>> for &MLPoly<F> {
+    type Output = Result<MLPoly<F>>;
+
+    fn sub(self, rhs: &MLPoly<F>) -> Result<MLPoly<F>> {
+        self.try_sub(rhs)
+    }
+}
+
+/// f += g
+///
+/// # Panics
+/// Panics if f and g have different numbers of variables; use
+/// `try_add_assign` to get an error instead
+impl<F: Field> AddAssign<&MLPoly<F>> for MLPoly<F> {
+    fn add_assign(&mut self, rhs: &MLPoly<F>) {
+        if let Err(e) = self.try_add_assign(rhs) {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// f -= g
+///
+/// # Panics
+/// Panics if f and g have different numbers of variables; use
+/// `try_sub_assign` to get an error instead
+impl<F: Field> SubAssign<&MLPoly<F>> for MLPoly<F> {
+    fn sub_assign(&mut self, rhs: &MLPoly<F>) {
+        if let Err(e) = self.try_sub_assign(rhs) {
+            panic!("{}", e);
+        }
+    }
+}
+
+impl<F: Field> MulAssign<F> for MLPoly<F> {
+    fn mul_assign(&mut self, rhs: F) {
+        for a in self.evals.iter_mut() {
+            *a *= rhs;
+        }
+    }
+}
+
+impl<F: Field> Neg for MLPoly<F> {
+    type Output = MLPoly<F>;
+
+    fn neg(mut self) -> MLPoly<F> {
+        for a in self.evals.iter_mut() {
+            *a = -*a;
+        }
+        self
+    }
+}
+
+impl<F: Field> Neg for &MLPoly<F> {
+    type Output = MLPoly<F>;
+
+    fn neg(self) -> MLPoly<F> {
+        -self.clone()
+    }
+}
+
+impl<F: Field> Mul<F> for MLPoly<F> {
+    type Output = MLPoly<F>;
+
+    fn mul(mut self, rhs: F) -> MLPoly<F> {
+        self *= rhs;
+        self
+    }
+}
+
+impl<F: Field> Mul<F> for &MLPoly<F> {
+    type Output = MLPoly<F>;
+
+    fn mul(self, rhs: F) -> MLPoly<F> {
+        self.clone() * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        MLPoly::from_evals((0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect())
+    }
+
+    #[test]
+    fn test_operators_commute_with_evaluation() {
+        let mut rng = ark_std::test_rng();
+        let f = random_poly(5);
+        let g = MLPoly::from_evals(f.evals.iter().rev().copied().collect());
+        let c = Fr::rand(&mut rng);
+        let x: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+        let (fx, gx) = (f.eval_at(&x), g.eval_at(&x));
+
+        assert_eq!(f.try_add(&g).unwrap().eval_at(&x), fx + gx);
+        assert_eq!(f.try_sub(&g).unwrap().eval_at(&x), fx - gx);
+        assert_eq!((&f + &g).unwrap(), f.try_add(&g).unwrap());
+        assert_eq!((&f - &g).unwrap(), f.try_sub(&g).unwrap());
+        assert_eq!((f.clone() + &g).unwrap(), f.try_add(&g).unwrap());
+        assert_eq!((f.clone() - &g).unwrap(), f.try_sub(&g).unwrap());
+        assert_eq!((-&f).eval_at(&x), -fx);
+        assert_eq!((&f * c).eval_at(&x), fx * c);
+
+        let mut h = f.clone();
+        h.try_add_assign(&g).unwrap();
+        h.try_sub_assign(&f).unwrap();
+        assert_eq!(h, g);
+        h += &f;
+        h -= &g;
+        assert_eq!(h, f);
+
+        let combo = MLPoly::linear_combination(&[(c, &f), (Fr::from(3u64), &g)]).unwrap();
+        assert_eq!(combo.eval_at(&x), c * fx + Fr::from(3u64) * gx);
+    }
+
+    #[test]
+    fn test_hadamard() {
+        let f = random_poly(4);
+        let g = random_poly(4);
+        let prod = f.hadamard(&g).unwrap();
+        for (i, p) in prod.iter().enumerate() {
+            assert_eq!(*p, f.evals[i] * g.evals[i]);
+        }
+    }
+
+    #[test]
+    fn test_dimension_mismatch_is_an_error() {
+        let f = random_poly(3);
+        let g = random_poly(4);
+        let err = Error::DimensionMismatch { left: 3, right: 4 };
+        assert_eq!(f.try_add(&g), Err(err.clone()));
+        assert_eq!(f.try_sub(&g), Err(err.clone()));
+        assert_eq!(&f + &g, Err(err.clone()));
+        assert_eq!(f.clone() - &g, Err(err.clone()));
+        assert_eq!(f.hadamard(&g), Err(err.clone()));
+        assert_eq!(
+            MLPoly::linear_combination(&[(Fr::from(1u64), &f), (Fr::from(1u64), &g)]),
+            Err(err)
+        );
+        assert_eq!(
+            MLPoly::<Fr>::linear_combination(&[]),
+            Err(Error::EmptyCombination)
+        );
+
+        let mut h = f.clone();
+        assert!(h.try_add_assign(&g).is_err());
+        assert_eq!(h, f);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch")]
+    fn test_add_assign_mismatch_panics() {
+        let mut f = random_poly(3);
+        f += &random_poly(4);
+    }
+}
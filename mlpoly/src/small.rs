@@ -0,0 +1,201 @@
+//! Multilinear polynomials with small unsigned integer evaluations
+//!
+//! Witnesses made of bits, bytes or u32 limbs are wasteful to store as field
+//! elements. `SmallMLPoly` keeps them as integers: the first sumcheck round is
+//! an integer sum (accumulated in u128, exact for n < 64), and conversion to F
+//! happens only when the first variable is bound to a field challenge. Pairs
+//! that differ by 0 or ±1 (all pairs of a boolean table) fold without a field
+//! multiplication, and values below 256 are lifted by table lookup.
+
+use ark_ff::Field;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::MLPoly;
+
+/// Integer types usable as evaluations of a `SmallMLPoly`
+pub trait SmallValue: Copy + Into<u64> + Send + Sync {}
+
+impl SmallValue for bool {}
+impl SmallValue for u8 {}
+impl SmallValue for u16 {}
+impl SmallValue for u32 {}
+impl SmallValue for u64 {}
+
+/// Multilinear polynomial in evaluation form over small integers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmallMLPoly<T: SmallValue> {
+    pub n_vars: usize,
+    pub evals: Vec<T>,
+}
+
+/// Field images of 0, ..., 255, so that most small values skip the Montgomery conversion
+fn byte_table<F: Field>() -> Vec<F> {
+    let mut table = Vec::with_capacity(256);
+    let mut cur = F::ZERO;
+    for _ in 0..256 {
+        table.push(cur);
+        cur += F::ONE;
+    }
+    table
+}
+
+#[inline]
+fn lift<F: Field>(v: u64, table: &[F]) -> F {
+    match table.get(v as usize) {
+        Some(&f) => f,
+        None => F::from(v),
+    }
+}
+
+/// lo + r · (hi - lo), skipping the multiplication when |hi - lo| ≤ 1
+#[inline]
+fn fold_pair<F: Field>(lo: u64, hi: u64, r: F, table: &[F]) -> F {
+    let base = lift(lo, table);
+    match hi.cmp(&lo) {
+        std::cmp::Ordering::Equal => base,
+        std::cmp::Ordering::Greater if hi - lo == 1 => base + r,
+        std::cmp::Ordering::Greater => base + r * lift(hi - lo, table),
+        std::cmp::Ordering::Less if lo - hi == 1 => base - r,
+        std::cmp::Ordering::Less => base - r * lift(lo - hi, table),
+    }
+}
+
+impl<T: SmallValue> SmallMLPoly<T> {
+    /// Create from evaluations
+    ///
+    /// # Panics
+    /// Panics if evals.len() is not a power of 2
+    pub fn from_evals(evals: Vec<T>) -> Self {
+        assert!(
+            evals.len().is_power_of_two(),
+            "evals length must be a power of 2"
+        );
+        let n_vars = evals.len().trailing_zeros() as usize;
+        Self { n_vars, evals }
+    }
+
+    /// Number of evaluations: 2^n_vars
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.evals.len()
+    }
+
+    /// Returns true if there are no evaluations
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.evals.is_empty()
+    }
+
+    /// Integer sums (∑ f(0, x'), ∑ f(1, x')) of the first round
+    pub fn round_sums(&self) -> (u128, u128) {
+        #[cfg(feature = "parallel")]
+        {
+            self.evals
+                .par_chunks_exact(2)
+                .map(|pair| (pair[0].into() as u128, pair[1].into() as u128))
+                .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1))
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.evals.chunks_exact(2).fold((0u128, 0u128), |acc, pair| {
+                (
+                    acc.0 + pair[0].into() as u128,
+                    acc.1 + pair[1].into() as u128,
+                )
+            })
+        }
+    }
+
+    /// (g(0), g(1)) of the first round, as in `MLPoly::round_sum_g0_g1`
+    pub fn round_sum_g0_g1<F: Field>(&self) -> (F, F) {
+        let (g0, g1) = self.round_sums();
+        (F::from(g0), F::from(g1))
+    }
+
+    /// Sum of all evaluations: ∑_{x ∈ {0,1}^n} f(x)
+    pub fn sum_all<F: Field>(&self) -> F {
+        F::from(self.evals.iter().map(|&e| e.into() as u128).sum::<u128>())
+    }
+
+    /// Fold the first variable at a field point r
+    ///
+    /// Returns f(r, x_2, ..., x_n) as an ordinary `MLPoly<F>`.
+    pub fn fold_first_var<F: Field>(&self, r: F) -> MLPoly<F> {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let table = byte_table::<F>();
+
+        #[cfg(feature = "parallel")]
+        let evals: Vec<F> = self
+            .evals
+            .par_chunks(2)
+            .map(|pair| fold_pair(pair[0].into(), pair[1].into(), r, &table))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let evals: Vec<F> = self
+            .evals
+            .chunks(2)
+            .map(|pair| fold_pair(pair[0].into(), pair[1].into(), r, &table))
+            .collect();
+
+        MLPoly {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    /// Lift every evaluation into F
+    pub fn to_mlpoly<F: Field>(&self) -> MLPoly<F> {
+        MLPoly {
+            n_vars: self.n_vars,
+            evals: self.evals.iter().map(|&e| F::from(e.into())).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::rand::Rng;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn test_boolean_matches_lifted() {
+        let mut rng = ark_std::test_rng();
+        let bits: Vec<bool> = (0..64).map(|_| rng.gen()).collect();
+        let small = SmallMLPoly::from_evals(bits);
+        let lifted: MLPoly<Fr> = small.to_mlpoly();
+
+        assert_eq!(small.sum_all::<Fr>(), lifted.sum_all());
+        assert_eq!(small.round_sum_g0_g1::<Fr>(), lifted.round_sum_g0_g1());
+        let r = Fr::rand(&mut rng);
+        assert_eq!(small.fold_first_var(r), lifted.fold_first_var(r));
+    }
+
+    #[test]
+    fn test_u64_extremes_match_lifted() {
+        // Large differences in both directions, and sums that overflow u64
+        let evals = vec![u64::MAX, 0, 3, u64::MAX, 7, 7, 5, 4];
+        let small = SmallMLPoly::from_evals(evals);
+        let lifted: MLPoly<Fr> = small.to_mlpoly();
+
+        assert_eq!(small.sum_all::<Fr>(), lifted.sum_all());
+        assert_eq!(small.round_sum_g0_g1::<Fr>(), lifted.round_sum_g0_g1());
+        let r = Fr::rand(&mut ark_std::test_rng());
+        assert_eq!(small.fold_first_var(r), lifted.fold_first_var(r));
+    }
+
+    #[test]
+    fn test_constant_round_sums_are_zero() {
+        // No first variable to split on, as for `MLPoly::round_sum_g0_g1`
+        let small = SmallMLPoly::from_evals(vec![9u64]);
+        assert_eq!(small.round_sums(), (0, 0));
+        assert_eq!(
+            small.round_sum_g0_g1::<Fr>(),
+            small.to_mlpoly::<Fr>().round_sum_g0_g1()
+        );
+    }
+}
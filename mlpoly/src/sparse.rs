@@ -0,0 +1,327 @@
+//! Sparse multilinear polynomials: only the nonzero evaluations are stored
+//!
+//! Entries are (index, value) pairs sorted by index, with the usual LSB-first
+//! indexing (x_1 is bit 0 of the index). Binding x_1 merges the entries at
+//! 2j and 2j + 1, which are adjacent in the sorted list, so every operation
+//! costs O(nnz) instead of O(2^n).
+
+use ark_ff::Field;
+
+use crate::multilinear::MultilinearPolynomial;
+use crate::MLPoly;
+
+/// Multilinear polynomial given by its nonzero evaluations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMLPoly<F: Field> {
+    /// Number of variables
+    pub n_vars: usize,
+    /// (index, f(index)) for every nonzero evaluation, sorted by index
+    pub entries: Vec<(usize, F)>,
+}
+
+impl<F: Field> SparseMLPoly<F> {
+    /// Create from (index, value) pairs in any order
+    ///
+    /// Values at a repeated index are added up; zero values are dropped.
+    ///
+    /// # Panics
+    /// Panics if an index is not below 2^n_vars
+    pub fn new(n_vars: usize, mut entries: Vec<(usize, F)>) -> Self {
+        assert!(
+            entries.iter().all(|&(i, _)| i < 1 << n_vars),
+            "index out of range for {} variables",
+            n_vars
+        );
+        entries.sort_unstable_by_key(|&(i, _)| i);
+
+        let mut merged: Vec<(usize, F)> = Vec::with_capacity(entries.len());
+        for (i, v) in entries {
+            match merged.last_mut() {
+                Some(last) if last.0 == i => last.1 += v,
+                _ => merged.push((i, v)),
+            }
+        }
+        merged.retain(|(_, v)| !v.is_zero());
+        Self {
+            n_vars,
+            entries: merged,
+        }
+    }
+
+    /// Keep the nonzero evaluations of a dense polynomial
+    pub fn from_dense(poly: &MLPoly<F>) -> Self {
+        let entries = poly
+            .evals
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_zero())
+            .map(|(i, &v)| (i, v))
+            .collect();
+        Self {
+            n_vars: poly.n_vars,
+            entries,
+        }
+    }
+
+    /// Expand to all 2^n evaluations
+    pub fn to_dense(&self) -> MLPoly<F> {
+        let mut poly = MLPoly::zero(self.n_vars);
+        for &(i, v) in &self.entries {
+            poly.evals[i] = v;
+        }
+        poly
+    }
+
+    /// Number of stored (nonzero) evaluations
+    #[inline]
+    pub fn nnz(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Evaluation at index i (zero if not stored)
+    pub fn get(&self, index: usize) -> F {
+        self.entries
+            .binary_search_by_key(&index, |&(i, _)| i)
+            .map_or(F::ZERO, |pos| self.entries[pos].1)
+    }
+
+    /// Sum of all evaluations: ∑_{x ∈ {0,1}^n} f(x)
+    pub fn sum_all(&self) -> F {
+        self.entries.iter().map(|(_, v)| v).sum()
+    }
+
+    /// Compute g(0) and g(1) for the round polynomial in sumcheck
+    pub fn round_sum_g0_g1(&self) -> (F, F) {
+        let mut sums = [F::ZERO; 2];
+        for &(i, v) in &self.entries {
+            sums[i & 1] += v;
+        }
+        (sums[0], sums[1])
+    }
+
+    /// Fold the first variable at point r
+    ///
+    /// Returns f(r, x_2, ..., x_n); an absent partner counts as zero.
+    pub fn fold_first_var(&self, r: F) -> Self {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let one_minus_r = F::ONE - r;
+
+        let mut entries = Vec::with_capacity(self.entries.len());
+        let mut k = 0;
+        while k < self.entries.len() {
+            let (i, v) = self.entries[k];
+            let folded = if i & 1 == 1 {
+                // f(0, ..) = 0
+                k += 1;
+                v * r
+            } else if k + 1 < self.entries.len() && self.entries[k + 1].0 == i + 1 {
+                let hi = self.entries[k + 1].1;
+                k += 2;
+                v + r * (hi - v)
+            } else {
+                // f(1, ..) = 0
+                k += 1;
+                v * one_minus_r
+            };
+            if !folded.is_zero() {
+                entries.push((i >> 1, folded));
+            }
+        }
+
+        Self {
+            n_vars: self.n_vars - 1,
+            entries,
+        }
+    }
+
+    /// Compute (g(0), g(1)) for a round that binds the last variable
+    pub fn round_sum_last_var(&self) -> (F, F) {
+        assert!(self.n_vars > 0, "a constant polynomial has no round");
+        let half = 1 << (self.n_vars - 1);
+        let mut sums = [F::ZERO; 2];
+        for &(i, v) in &self.entries {
+            sums[(i >= half) as usize] += v;
+        }
+        (sums[0], sums[1])
+    }
+
+    /// Fold the last variable at point r
+    ///
+    /// Returns f(x_1, ..., x_{n-1}, r). The entries below 2^{n-1} and those
+    /// above are both sorted, so they are merged like two sorted lists.
+    pub fn fold_last_var(&self, r: F) -> Self {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let half = 1 << (self.n_vars - 1);
+        let split = self.entries.partition_point(|&(i, _)| i < half);
+        let (lo, hi) = self.entries.split_at(split);
+        let one_minus_r = F::ONE - r;
+
+        let mut entries = Vec::with_capacity(self.entries.len());
+        let (mut a, mut b) = (0, 0);
+        while a < lo.len() || b < hi.len() {
+            let lo_index = lo.get(a).map_or(usize::MAX, |&(i, _)| i);
+            let hi_index = hi.get(b).map_or(usize::MAX, |&(i, _)| i - half);
+            let (index, folded) = match lo_index.cmp(&hi_index) {
+                std::cmp::Ordering::Less => {
+                    a += 1;
+                    (lo_index, lo[a - 1].1 * one_minus_r)
+                }
+                std::cmp::Ordering::Greater => {
+                    b += 1;
+                    (hi_index, hi[b - 1].1 * r)
+                }
+                std::cmp::Ordering::Equal => {
+                    let (v, w) = (lo[a].1, hi[b].1);
+                    a += 1;
+                    b += 1;
+                    (lo_index, v + r * (w - v))
+                }
+            };
+            if !folded.is_zero() {
+                entries.push((index, folded));
+            }
+        }
+
+        Self {
+            n_vars: self.n_vars - 1,
+            entries,
+        }
+    }
+
+    /// Evaluate at a point x ∈ F^n in O(nnz · n)
+    pub fn eval_at(&self, x: &[F]) -> F {
+        assert_eq!(
+            x.len(),
+            self.n_vars,
+            "wrong number of evaluation points: given {}, expected {}",
+            x.len(),
+            self.n_vars
+        );
+        // f(x) = ∑_i f(i) · eq(x, bits of i)
+        self.entries
+            .iter()
+            .map(|&(i, v)| {
+                x.iter().enumerate().fold(v, |acc, (k, &x_k)| {
+                    if (i >> k) & 1 == 1 {
+                        acc * x_k
+                    } else {
+                        acc * (F::ONE - x_k)
+                    }
+                })
+            })
+            .sum()
+    }
+}
+
+impl<F: Field> MultilinearPolynomial<F> for SparseMLPoly<F> {
+    type Folded = SparseMLPoly<F>;
+
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn round_sum_g0_g1(&self) -> (F, F) {
+        SparseMLPoly::round_sum_g0_g1(self)
+    }
+
+    fn fold_first_var(&self, r: F) -> Self {
+        SparseMLPoly::fold_first_var(self, r)
+    }
+
+    fn round_sum_last_var(&self) -> (F, F) {
+        SparseMLPoly::round_sum_last_var(self)
+    }
+
+    fn fold_last_var(&self, r: F) -> Self {
+        SparseMLPoly::fold_last_var(self, r)
+    }
+
+    fn sum_all(&self) -> F {
+        SparseMLPoly::sum_all(self)
+    }
+
+    fn eval_at(&self, x: &[F]) -> F {
+        SparseMLPoly::eval_at(self, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::rand::Rng;
+    use ark_std::UniformRand;
+
+    /// Roughly one in eight entries nonzero, including lone even and odd entries
+    fn random_sparse(n_vars: usize) -> SparseMLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        let mut entries = Vec::new();
+        for i in 0..1usize << n_vars {
+            if rng.gen_ratio(1, 8) {
+                entries.push((i, Fr::rand(&mut rng)));
+            }
+        }
+        SparseMLPoly::new(n_vars, entries)
+    }
+
+    #[test]
+    fn test_new_merges_and_drops_zeros() {
+        let one = Fr::from(1u64);
+        let poly = SparseMLPoly::new(
+            3,
+            vec![
+                (5, one),
+                (2, one),
+                (5, one),
+                (7, one),
+                (7, -one),
+                (0, Fr::from(0u64)),
+            ],
+        );
+        assert_eq!(poly.entries, vec![(2, one), (5, Fr::from(2u64))]);
+        assert_eq!(poly.get(5), Fr::from(2u64));
+        assert_eq!(poly.get(7), Fr::from(0u64));
+    }
+
+    #[test]
+    fn test_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let sparse = random_sparse(8);
+        let dense = sparse.to_dense();
+        assert_eq!(SparseMLPoly::from_dense(&dense), sparse);
+
+        assert_eq!(sparse.sum_all(), dense.sum_all());
+        assert_eq!(sparse.round_sum_g0_g1(), dense.round_sum_g0_g1());
+
+        let x: Vec<Fr> = (0..8).map(|_| Fr::rand(&mut rng)).collect();
+        assert_eq!(sparse.eval_at(&x), dense.eval_at(&x));
+    }
+
+    #[test]
+    fn test_fold_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let mut sparse = random_sparse(7);
+        let mut dense = sparse.to_dense();
+        while sparse.n_vars > 0 {
+            let r = Fr::rand(&mut rng);
+            sparse = sparse.fold_first_var(r);
+            dense = dense.fold_first_var(r);
+            assert_eq!(sparse.to_dense(), dense);
+            assert!(sparse.nnz() <= 1 << sparse.n_vars);
+        }
+    }
+
+    #[test]
+    fn test_fold_last_matches_dense() {
+        let mut rng = ark_std::test_rng();
+        let mut sparse = random_sparse(7);
+        let mut dense = sparse.to_dense();
+        while sparse.n_vars > 0 {
+            assert_eq!(sparse.round_sum_last_var(), dense.round_sum_last_var());
+            let r = Fr::rand(&mut rng);
+            sparse = sparse.fold_last_var(r);
+            dense = dense.fold_last_var(r);
+            assert_eq!(sparse.to_dense(), dense);
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! Binding, reordering and padding the variables of an `MLPoly`
+//!
+//! Variables are numbered from 0: variable k is x_{k+1}, i.e. bit k of an
+//! evaluation index. Every method returns a polynomial in the same LSB-first
+//! layout, so its evaluations can be fed straight back to the other methods.
+
+use ark_ff::Field;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::MLPoly;
+
+impl<F: Field> MLPoly<F> {
+    /// Fold the last variable at point r
+    ///
+    /// Returns f(x_1, ..., x_{n-1}, r). The two halves of the evaluation table
+    /// are f(.., 0) and f(.., 1), so this is the contiguous counterpart of
+    /// `fold_first_var` used when binding from the highest variable down.
+    pub fn fold_last_var(&self, r: F) -> Self {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let (lo, hi) = self.evals.split_at(self.len() / 2);
+
+        #[cfg(feature = "parallel")]
+        let evals: Vec<F> = lo
+            .par_iter()
+            .zip(hi)
+            .map(|(&a, &b)| a + r * (b - a))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let evals: Vec<F> = lo.iter().zip(hi).map(|(&a, &b)| a + r * (b - a)).collect();
+
+        Self {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    /// Compute (g(0), g(1)) for a sumcheck round that binds the last variable
+    ///
+    /// Returns the sums of the lower and upper halves of the evaluation table:
+    /// g(t) = ∑_{x_1,...,x_{n-1}} f(x_1, ..., x_{n-1}, t).
+    pub fn round_sum_last_var(&self) -> (F, F) {
+        assert!(self.n_vars > 0, "a constant polynomial has no round");
+        let (lo, hi) = self.evals.split_at(self.len() / 2);
+
+        #[cfg(feature = "parallel")]
+        let sums = (
+            lo.par_iter().copied().reduce(|| F::ZERO, |a, b| a + b),
+            hi.par_iter().copied().reduce(|| F::ZERO, |a, b| a + b),
+        );
+
+        #[cfg(not(feature = "parallel"))]
+        let sums = (lo.iter().sum(), hi.iter().sum());
+
+        sums
+    }
+
+    /// Fold the last variable at point r, reusing the evaluation buffer
+    ///
+    /// Same result as `fold_last_var`; the lower half of `evals` is overwritten
+    /// and the upper half dropped.
+    pub fn fold_last_var_in_place(&mut self, r: F) {
+        assert!(self.n_vars > 0, "cannot fold a constant polynomial");
+        let half = self.len() / 2;
+        let (lo, hi) = self.evals.split_at_mut(half);
+
+        #[cfg(feature = "parallel")]
+        lo.par_iter_mut()
+            .zip(&*hi)
+            .for_each(|(a, &b)| *a += r * (b - *a));
+
+        #[cfg(not(feature = "parallel"))]
+        for (a, &b) in lo.iter_mut().zip(&*hi) {
+            *a += r * (b - *a);
+        }
+
+        self.evals.truncate(half);
+        self.n_vars -= 1;
+    }
+
+    /// Fold the last variable at r in place and return the next round's (g(0), g(1))
+    ///
+    /// Equivalent to `fold_last_var_in_place(r)` followed by `round_sum_last_var()`
+    /// in a single pass: folded values below the new midpoint go to g(0), the
+    /// others to g(1).
+    ///
+    /// # Panics
+    /// Panics if fewer than two variables are left
+    pub fn fold_last_and_round_sum(&mut self, r: F) -> (F, F) {
+        assert!(
+            self.n_vars >= 2,
+            "need two variables to fold and compute the next round"
+        );
+        let half = self.len() / 2;
+        let quarter = half / 2;
+        let (lo, hi) = self.evals.split_at_mut(half);
+
+        #[cfg(feature = "parallel")]
+        let sums = lo
+            .par_iter_mut()
+            .zip(&*hi)
+            .enumerate()
+            .map(|(i, (a, &b))| {
+                *a += r * (b - *a);
+                if i < quarter {
+                    (*a, F::ZERO)
+                } else {
+                    (F::ZERO, *a)
+                }
+            })
+            .reduce(|| (F::ZERO, F::ZERO), |a, b| (a.0 + b.0, a.1 + b.1));
+
+        #[cfg(not(feature = "parallel"))]
+        let sums = {
+            let mut sums = [F::ZERO; 2];
+            for (i, (a, &b)) in lo.iter_mut().zip(&*hi).enumerate() {
+                *a += r * (b - *a);
+                sums[(i >= quarter) as usize] += *a;
+            }
+            (sums[0], sums[1])
+        };
+
+        self.evals.truncate(half);
+        self.n_vars -= 1;
+        sums
+    }
+
+    /// Fold variable `var` at point r
+    ///
+    /// Returns f with x_{var+1} = r, a polynomial in the remaining n - 1
+    /// variables (in their original order).
+    ///
+    /// # Panics
+    /// Panics if `var >= n_vars`
+    pub fn fold_var(&self, var: usize, r: F) -> Self {
+        self.map_var_halves(var, |lo, hi| lo + r * (hi - lo))
+    }
+
+    /// Fix variable `var` to a boolean value
+    ///
+    /// Same as `fold_var(var, 0 or 1)` but copies evaluations instead of
+    /// interpolating.
+    ///
+    /// # Panics
+    /// Panics if `var >= n_vars`
+    pub fn fix_var(&self, var: usize, value: bool) -> Self {
+        self.map_var_halves(var, |lo, hi| if value { hi } else { lo })
+    }
+
+    /// Combine the evaluations at x_{var+1} = 0 and x_{var+1} = 1 pointwise
+    fn map_var_halves(&self, var: usize, combine: impl Fn(F, F) -> F) -> Self {
+        assert!(
+            var < self.n_vars,
+            "variable {} out of range for {} variables",
+            var,
+            self.n_vars
+        );
+        let block = 1 << var;
+        let mut evals = Vec::with_capacity(self.len() / 2);
+        for chunk in self.evals.chunks(2 * block) {
+            let (lo, hi) = chunk.split_at(block);
+            evals.extend(lo.iter().zip(hi).map(|(&a, &b)| combine(a, b)));
+        }
+        Self {
+            n_vars: self.n_vars - 1,
+            evals,
+        }
+    }
+
+    /// Reorder the variables
+    ///
+    /// Returns g with g(y) = f(x) where x_{perm[k]+1} = y_{k+1}, i.e. variable k
+    /// of g is variable `perm[k]` of f.
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation of 0..n_vars
+    pub fn permute_vars(&self, perm: &[usize]) -> Self {
+        assert_eq!(
+            perm.len(),
+            self.n_vars,
+            "permutation has wrong length: given {}, expected {}",
+            perm.len(),
+            self.n_vars
+        );
+        let mut seen = vec![false; self.n_vars];
+        for &p in perm {
+            assert!(
+                p < self.n_vars && !seen[p],
+                "not a permutation of the variables"
+            );
+            seen[p] = true;
+        }
+
+        let evals = (0..self.len())
+            .map(|j| {
+                let i = perm
+                    .iter()
+                    .enumerate()
+                    .fold(0, |i, (k, &p)| i | (((j >> k) & 1) << p));
+                self.evals[i]
+            })
+            .collect();
+        Self {
+            n_vars: self.n_vars,
+            evals,
+        }
+    }
+
+    /// Append `extra` dummy variables that the polynomial does not depend on
+    ///
+    /// Returns g(x_1, ..., x_{n+extra}) = f(x_1, ..., x_n); the evaluation table
+    /// is f's repeated 2^extra times. Note that ∑ g = 2^extra · ∑ f.
+    pub fn extend_vars(&self, extra: usize) -> Self {
+        let mut evals = Vec::with_capacity(self.len() << extra);
+        for _ in 0..1usize << extra {
+            evals.extend_from_slice(&self.evals);
+        }
+        Self {
+            n_vars: self.n_vars + extra,
+            evals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        MLPoly::from_evals((0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect())
+    }
+
+    #[test]
+    fn test_fold_var_matches_evaluation() {
+        let mut rng = ark_std::test_rng();
+        let f = random_poly(5);
+        let x: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+
+        assert_eq!(f.fold_var(0, x[0]), f.fold_first_var(x[0]));
+        assert_eq!(f.fold_var(4, x[4]), f.fold_last_var(x[4]));
+        for var in 0..5 {
+            let mut rest = x.clone();
+            rest.remove(var);
+            assert_eq!(f.fold_var(var, x[var]).eval_at(&rest), f.eval_at(&x));
+
+            assert_eq!(f.fix_var(var, false), f.fold_var(var, Fr::from(0u64)));
+            assert_eq!(f.fix_var(var, true), f.fold_var(var, Fr::from(1u64)));
+        }
+    }
+
+    #[test]
+    fn test_fold_last_var_repeatedly_evaluates() {
+        let mut rng = ark_std::test_rng();
+        let f = random_poly(4);
+        let x: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+        let mut g = f.clone();
+        for &r in x.iter().rev() {
+            g = g.fold_last_var(r);
+        }
+        assert_eq!(g.evals[0], f.eval_at(&x));
+    }
+
+    #[test]
+    fn test_last_var_in_place_and_fused() {
+        let mut rng = ark_std::test_rng();
+        let f = random_poly(6);
+        let r = Fr::rand(&mut rng);
+
+        let (g0, g1) = f.round_sum_last_var();
+        assert_eq!(g0 + g1, f.sum_all());
+        assert_eq!(g0, f.fix_var(5, false).sum_all());
+
+        let mut in_place = f.clone();
+        in_place.fold_last_var_in_place(r);
+        assert_eq!(in_place, f.fold_last_var(r));
+
+        let mut fused = f.clone();
+        let sums = fused.fold_last_and_round_sum(r);
+        assert_eq!(fused, in_place);
+        assert_eq!(sums, in_place.round_sum_last_var());
+    }
+
+    #[test]
+    fn test_permute_and_extend() {
+        let mut rng = ark_std::test_rng();
+        let f = random_poly(4);
+        let y: Vec<Fr> = (0..4).map(|_| Fr::rand(&mut rng)).collect();
+
+        let perm = [2, 0, 3, 1];
+        let g = f.permute_vars(&perm);
+        let mut x = vec![Fr::from(0u64); 4];
+        for (k, &p) in perm.iter().enumerate() {
+            x[p] = y[k];
+        }
+        assert_eq!(g.eval_at(&y), f.eval_at(&x));
+        assert_eq!(f.permute_vars(&[0, 1, 2, 3]), f);
+
+        let h = f.extend_vars(2);
+        let mut padded = y.clone();
+        padded.extend([Fr::rand(&mut rng), Fr::rand(&mut rng)]);
+        assert_eq!(h.n_vars, 6);
+        assert_eq!(h.eval_at(&padded), f.eval_at(&y));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn test_permute_rejects_repeated_variable() {
+        random_poly(3).permute_vars(&[0, 1, 1]);
+    }
+}
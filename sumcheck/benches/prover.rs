@@ -0,0 +1,132 @@
+//! Prover benchmarks for n = 20..=24
+//!
+//! `round_loop` isolates the per-round polynomial work: `two_pass` sums and then
+//! folds (two sweeps over memory per round), `fused` folds and sums the next
+//! round in a single sweep. `prove` runs the full sumcheck prover.
+//! `boolean` compares proving a 0/1 table lifted into F with `prove_small`.
+//!
+//! Run with `cargo bench -p sumcheck --bench prover` (add `--features parallel`
+//! for the rayon build).
+
+use ark_bn254::Fr;
+use ark_std::rand::Rng;
+use ark_std::UniformRand;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use mlpoly::{MLPoly, SmallMLPoly};
+use sumcheck::{prove_owned, prove_small, Statement, SumcheckConfig, Transcript};
+
+const SIZES: [usize; 3] = [20, 22, 24];
+
+fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+    let mut rng = ark_std::test_rng();
+    MLPoly::from_evals((0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect())
+}
+
+fn bench_round_loop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_loop");
+    group.sample_size(10);
+
+    for n_vars in SIZES {
+        let poly = random_poly(n_vars);
+        let mut rng = ark_std::test_rng();
+        let challenges: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+
+        group.bench_with_input(BenchmarkId::new("two_pass", n_vars), &n_vars, |b, _| {
+            b.iter_batched(
+                || poly.clone(),
+                |mut p| {
+                    let mut acc = Fr::from(0u64);
+                    for &r in &challenges {
+                        let (g0, g1) = p.round_sum_g0_g1();
+                        acc += g0 + g1;
+                        p.fold_first_var_in_place(r);
+                    }
+                    acc
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("fused", n_vars), &n_vars, |b, _| {
+            b.iter_batched(
+                || poly.clone(),
+                |mut p| {
+                    let (g0, g1) = p.round_sum_g0_g1();
+                    let mut acc = g0 + g1;
+                    for &r in &challenges[..n_vars - 1] {
+                        let (g0, g1) = p.fold_and_round_sum(r);
+                        acc += g0 + g1;
+                    }
+                    p.fold_first_var_in_place(challenges[n_vars - 1]);
+                    acc
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_prove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prove");
+    group.sample_size(10);
+
+    for n_vars in SIZES {
+        let poly = random_poly(n_vars);
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let config = SumcheckConfig::default();
+
+        group.bench_with_input(BenchmarkId::from_parameter(n_vars), &n_vars, |b, _| {
+            b.iter_batched(
+                || poly.clone(),
+                |p| {
+                    let mut transcript = Transcript::new(b"bench");
+                    prove_owned(&stmt, p, &mut transcript, &config)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_boolean(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boolean");
+    group.sample_size(10);
+
+    for n_vars in SIZES {
+        let mut rng = ark_std::test_rng();
+        let small = SmallMLPoly::from_evals((0..1 << n_vars).map(|_| rng.gen::<bool>()).collect());
+        let lifted: MLPoly<Fr> = small.to_mlpoly();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: lifted.sum_all(),
+        };
+        let config = SumcheckConfig::default();
+
+        group.bench_with_input(BenchmarkId::new("lifted", n_vars), &n_vars, |b, _| {
+            b.iter_batched(
+                || lifted.clone(),
+                |p| {
+                    let mut transcript = Transcript::new(b"bench");
+                    prove_owned(&stmt, p, &mut transcript, &config)
+                },
+                BatchSize::LargeInput,
+            )
+        });
+
+        group.bench_with_input(BenchmarkId::new("small", n_vars), &n_vars, |b, _| {
+            b.iter(|| {
+                let mut transcript = Transcript::new(b"bench");
+                prove_small(&stmt, &small, &mut transcript, &config)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_round_loop, bench_prove, bench_boolean);
+criterion_main!(benches);
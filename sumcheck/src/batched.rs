@@ -0,0 +1,232 @@
+//! Batched sumcheck over many claims ∑_x f_i(x) = c_i
+//!
+//! Instances may have different numbers of variables. With N = max_i n_i, the
+//! instance f_i is viewed as a polynomial in N variables that does not depend on
+//! x_{n_i+1}, ..., x_N, so its sum over {0,1}^N is 2^{N - n_i} · c_i. The prover
+//! runs a single N-round sumcheck on ∑_i ρ_i · f_i with ρ_i drawn from the
+//! transcript; instance i is evaluated at the first n_i challenges.
+
+use ark_ff::PrimeField;
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::protocol::{absorb_statement, append_round_poly, verify_rounds};
+use crate::transcript::Transcript;
+use crate::types::{BatchedProof, RoundPoly, Statement, SumcheckConfig, SumcheckProof};
+
+/// Absorb every statement and draw one combination coefficient per instance
+fn batch_coeffs<F: PrimeField>(stmts: &[Statement<F>], transcript: &mut Transcript) -> Vec<F> {
+    transcript.append_message(b"protocol", b"batched-sumcheck");
+    transcript.append_u64(b"num_instances", stmts.len() as u64);
+    for stmt in stmts {
+        absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+    }
+    stmts
+        .iter()
+        .map(|_| transcript.challenge_scalar(b"batch_coeff"))
+        .collect()
+}
+
+/// [1, 2, 4, ..., 2^n]
+fn powers_of_two<F: PrimeField>(n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n + 1);
+    let mut cur = F::ONE;
+    for _ in 0..=n {
+        powers.push(cur);
+        cur.double_in_place();
+    }
+    powers
+}
+
+/// Generate one proof for all claims ∑_x f_i(x) = c_i
+///
+/// # Arguments
+/// * `stmts` - One statement per instance
+/// * `polys` - One multilinear polynomial per instance, with `stmts[i].n_vars` variables
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Panics
+/// Panics if the number of statements and polynomials differ or a polynomial
+/// does not match its statement
+pub fn prove_batched<F: PrimeField>(
+    stmts: &[Statement<F>],
+    polys: &[MLPoly<F>],
+    transcript: &mut Transcript,
+) -> BatchedProof<F> {
+    assert_eq!(stmts.len(), polys.len(), "one polynomial per statement");
+    for (stmt, poly) in stmts.iter().zip(polys) {
+        assert_eq!(
+            poly.n_vars, stmt.n_vars,
+            "polynomial has wrong number of variables"
+        );
+    }
+    let coeffs: Vec<F> = batch_coeffs(stmts, transcript);
+    let n_max = stmts.iter().map(|s| s.n_vars).max().unwrap_or(0);
+    let pow2 = powers_of_two::<F>(n_max);
+
+    let mut current: Vec<MLPoly<F>> = polys.to_vec();
+    let mut round_polys = Vec::with_capacity(n_max);
+
+    for round in 0..n_max {
+        let mut g0 = F::ZERO;
+        let mut g1 = F::ZERO;
+        for (poly, rho) in current.iter().zip(&coeffs) {
+            if poly.n_vars > 0 {
+                // Real variable: every assignment of the dummy variables repeats the sum
+                let scale = *rho * pow2[n_max - round - poly.n_vars];
+                let (h0, h1) = poly.round_sum_g0_g1();
+                g0 += scale * h0;
+                g1 += scale * h1;
+            } else {
+                // Dummy variable: the instance is the constant f_i(r_1, ..., r_{n_i})
+                let half = *rho * poly.evals[0] * pow2[n_max - round - 1];
+                g0 += half;
+                g1 += half;
+            }
+        }
+
+        let round_poly = RoundPoly::new(g0, g1);
+        append_round_poly(&round_poly, transcript);
+        round_polys.push(round_poly);
+
+        let r: F = transcript.challenge_scalar(b"r");
+        for poly in current.iter_mut().filter(|p| p.n_vars > 0) {
+            poly.fold_first_var_in_place(r);
+        }
+    }
+
+    let final_evals: Vec<F> = current.iter().map(|p| p.evals[0]).collect();
+    for e in &final_evals {
+        transcript.append_field(b"final_eval", e);
+    }
+
+    BatchedProof {
+        sumcheck: SumcheckProof { round_polys },
+        final_evals,
+    }
+}
+
+/// Verify a batched sumcheck proof
+///
+/// # Arguments
+/// * `stmts` - One statement per instance
+/// * `proof` - The batched proof
+/// * `oracles` - One oracle per instance; oracle i is queried at (r_1, ..., r_{n_i})
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the combined final claim or an oracle check fails
+/// * `Err(_)` if the shapes disagree or a round check fails
+pub fn verify_batched<F: PrimeField>(
+    stmts: &[Statement<F>],
+    proof: &BatchedProof<F>,
+    oracles: &[&dyn Oracle<F>],
+    transcript: &mut Transcript,
+) -> Result<bool> {
+    if oracles.len() != stmts.len() || proof.final_evals.len() != stmts.len() {
+        return Err(Error::DimensionMismatch("wrong number of instances"));
+    }
+    let coeffs: Vec<F> = batch_coeffs(stmts, transcript);
+    let n_max = stmts.iter().map(|s| s.n_vars).max().unwrap_or(0);
+    let pow2 = powers_of_two::<F>(n_max);
+
+    let claim: F = stmts
+        .iter()
+        .zip(&coeffs)
+        .map(|(stmt, rho)| *rho * pow2[n_max - stmt.n_vars] * stmt.claim_sum)
+        .sum();
+    let subclaim = verify_rounds(claim, n_max, 1, &proof.sumcheck, transcript)?;
+    for e in &proof.final_evals {
+        transcript.append_field(b"final_eval", e);
+    }
+
+    // The combined final claim splits into the per-instance evaluations...
+    let combined: F = coeffs
+        .iter()
+        .zip(&proof.final_evals)
+        .map(|(rho, e)| *rho * e)
+        .sum();
+    if combined != subclaim.expected_eval {
+        return Ok(false);
+    }
+
+    // ...each of which must match its own oracle on a prefix of the point
+    for ((stmt, oracle), e) in stmts.iter().zip(oracles).zip(&proof.final_evals) {
+        if oracle.query(&subclaim.point[..stmt.n_vars])? != *e {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn instances(n_vars: &[usize]) -> (Vec<Statement<Fr>>, Vec<MLPoly<Fr>>) {
+        let mut rng = ark_std::test_rng();
+        let polys: Vec<MLPoly<Fr>> = n_vars
+            .iter()
+            .map(|&n| MLPoly::from_evals((0..(1 << n)).map(|_| Fr::rand(&mut rng)).collect()))
+            .collect();
+        let stmts = polys
+            .iter()
+            .map(|p| Statement {
+                n_vars: p.n_vars,
+                claim_sum: p.sum_all(),
+            })
+            .collect();
+        (stmts, polys)
+    }
+
+    fn verify_with_polys(
+        stmts: &[Statement<Fr>],
+        proof: &BatchedProof<Fr>,
+        polys: &[MLPoly<Fr>],
+    ) -> Result<bool> {
+        let oracles: Vec<PolyOracle<Fr>> = polys.iter().cloned().map(PolyOracle::new).collect();
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"batched-test");
+        verify_batched(stmts, proof, &refs, &mut transcript)
+    }
+
+    #[test]
+    fn test_batched_mixed_sizes() {
+        let (stmts, polys) = instances(&[3, 1, 5, 0, 5]);
+
+        let mut transcript = Transcript::new(b"batched-test");
+        let proof = prove_batched(&stmts, &polys, &mut transcript);
+        assert_eq!(proof.sumcheck.num_rounds(), 5);
+        assert_eq!(proof.final_evals.len(), 5);
+
+        assert!(verify_with_polys(&stmts, &proof, &polys).unwrap());
+    }
+
+    #[test]
+    fn test_batched_one_wrong_claim_fails() {
+        let (mut stmts, polys) = instances(&[2, 4, 3]);
+        stmts[1].claim_sum += Fr::from(1u64);
+
+        let mut transcript = Transcript::new(b"batched-test");
+        let proof = prove_batched(&stmts, &polys, &mut transcript);
+
+        assert_ne!(verify_with_polys(&stmts, &proof, &polys), Ok(true));
+    }
+
+    #[test]
+    fn test_batched_tampered_final_eval_fails() {
+        let (stmts, polys) = instances(&[2, 3]);
+
+        let mut transcript = Transcript::new(b"batched-test");
+        let mut proof = prove_batched(&stmts, &polys, &mut transcript);
+
+        // A wrong per-instance evaluation no longer matches the combined final claim
+        proof.final_evals[0] += Fr::from(1u64);
+        assert_eq!(verify_with_polys(&stmts, &proof, &polys), Ok(false));
+    }
+}
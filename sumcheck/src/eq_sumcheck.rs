@@ -0,0 +1,300 @@
+//! Sumcheck for ∑_x eq(r, x) · f(x) without materializing eq(r, ·)
+//!
+//! In round i the round polynomial factors as
+//! s_i(X) = C_i · eq(r_i, X) · q_i(X), where C_i = ∏_{j<i} eq(r_j, ρ_j) is a scalar
+//! and q_i(X) = ∑_y eq(r_{>i}, y) · f(ρ_1, ..., ρ_{i-1}, X, y) is linear in X
+//! (Gruen's optimization). The weights eq(r_{>i}, y) are in turn split as
+//! eq(r_lo, y_lo) · eq(r_hi, y_hi) over the two halves of the variables, so the
+//! prover only ever holds two tables of size ~2^{n/2} next to f.
+//!
+//! The round polynomials are those of the product sumcheck of
+//! `[MLPoly::eq_table(r), f]`; the transcript additionally binds r right after
+//! the statement, so the challenges depend on the eq point being claimed.
+
+use std::borrow::Cow;
+
+use ark_ff::PrimeField;
+use mlpoly::MLPoly;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::error::{Error, Result};
+use crate::oracle::{EqOracle, Oracle};
+use crate::product::product_composition;
+use crate::protocol::{absorb_statement, append_round_poly, verify_rounds};
+use crate::transcript::Transcript;
+use crate::types::{ProverOutput, RoundPoly, Statement, SumcheckConfig, SumcheckProof};
+
+/// eq(a, b) for single field elements
+#[inline]
+fn eq_1<F: PrimeField>(a: F, b: F) -> F {
+    a * b + (F::ONE - a) * (F::ONE - b)
+}
+
+/// Absorb the eq point r, which is part of the statement
+fn absorb_eq_point<F: PrimeField>(r: &[F], transcript: &mut Transcript) {
+    for r_i in r {
+        transcript.append_field(b"eq_point", r_i);
+    }
+}
+
+/// Compute q(0) = ∑_y eq(r_{>i}, y) f(0, y) and q(1) = ∑_y eq(r_{>i}, y) f(1, y)
+///
+/// `r_rest` are the eq coordinates of the variables still unbound after this
+/// round; `split` is how many of them belong to the low half.
+fn split_eq_round_sums<F: PrimeField>(poly: &MLPoly<F>, r_rest: &[F], split: usize) -> (F, F) {
+    let e_lo = MLPoly::eq_table(&r_rest[..split]);
+    let e_hi = MLPoly::eq_table(&r_rest[split..]);
+    let lo_len = e_lo.len();
+
+    let inner = |y_hi: usize, w_hi: &F| {
+        let mut inner0 = F::ZERO;
+        let mut inner1 = F::ZERO;
+        for (y_lo, w_lo) in e_lo.evals.iter().enumerate() {
+            let y = y_lo + y_hi * lo_len;
+            inner0 += *w_lo * poly.evals[2 * y];
+            inner1 += *w_lo * poly.evals[2 * y + 1];
+        }
+        (*w_hi * inner0, *w_hi * inner1)
+    };
+
+    #[cfg(feature = "parallel")]
+    let sums = e_hi
+        .evals
+        .par_iter()
+        .enumerate()
+        .map(|(y_hi, w_hi)| inner(y_hi, w_hi))
+        .reduce(|| (F::ZERO, F::ZERO), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    #[cfg(not(feature = "parallel"))]
+    let sums = e_hi
+        .evals
+        .iter()
+        .enumerate()
+        .map(|(y_hi, w_hi)| inner(y_hi, w_hi))
+        .fold((F::ZERO, F::ZERO), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    sums
+}
+
+/// Generate a sumcheck proof for ∑_x eq(r, x) · f(x)
+///
+/// # Arguments
+/// * `stmt` - Public statement; `claim_sum` is the claimed value of ∑_x eq(r, x) f(x) = f̃(r)
+/// * `r` - The eq point, of length `stmt.n_vars`
+/// * `poly` - The multilinear polynomial f
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The proof (degree-2 round polynomials), the challenge point ρ and the final
+/// evaluations `[eq(r, ρ), f(ρ)]`
+pub fn prove_eq_weighted<F: PrimeField>(
+    stmt: &Statement<F>,
+    r: &[F],
+    poly: &MLPoly<F>,
+    transcript: &mut Transcript,
+) -> ProverOutput<F> {
+    let n_vars = stmt.n_vars;
+    assert_eq!(r.len(), n_vars, "eq point has wrong number of variables");
+    assert_eq!(
+        poly.n_vars, n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+    absorb_eq_point(r, transcript);
+    product_composition::<F>(2).absorb(transcript);
+
+    let mid = n_vars / 2;
+    // Round 1 reads `poly` directly; the first fold allocates half of it, which
+    // later rounds fold in place
+    let mut current = Cow::Borrowed(poly);
+    let mut scale = F::ONE;
+    let mut round_polys = Vec::with_capacity(n_vars);
+    let mut point = Vec::with_capacity(n_vars);
+
+    for i in 0..n_vars {
+        // Unbound variables after this round are i+1..n; those below `mid` form the low half
+        let split = mid.saturating_sub(i + 1);
+        let (q0, q1) = split_eq_round_sums(&current, &r[i + 1..], split);
+
+        // s(t) = C · eq(r_i, t) · q(t) at t = 0, 1, 2
+        let evals = (0..3u64)
+            .map(|t| {
+                let t = F::from(t);
+                scale * eq_1(r[i], t) * (q0 + t * (q1 - q0))
+            })
+            .collect();
+        let round_poly = RoundPoly::from_evals(evals);
+        append_round_poly(&round_poly, transcript);
+        round_polys.push(round_poly);
+
+        let rho: F = transcript.challenge_scalar(b"r");
+        point.push(rho);
+
+        scale *= eq_1(r[i], rho);
+        match &mut current {
+            Cow::Borrowed(p) => current = Cow::Owned(p.fold_first_var(rho)),
+            Cow::Owned(p) => p.fold_first_var_in_place(rho),
+        }
+    }
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![scale, current.evals[0]],
+    }
+}
+
+/// Verify a sumcheck proof for ∑_x eq(r, x) · f(x)
+///
+/// The verifier evaluates eq(r, ρ) itself and queries `oracle` for f(ρ).
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final oracle check fails
+/// * `Err(_)` if `r` does not have `stmt.n_vars` coordinates or a round check fails
+pub fn verify_eq_weighted<F: PrimeField>(
+    stmt: &Statement<F>,
+    r: &[F],
+    proof: &SumcheckProof<F>,
+    oracle: &dyn Oracle<F>,
+    transcript: &mut Transcript,
+) -> Result<bool> {
+    if r.len() != stmt.n_vars {
+        return Err(Error::DimensionMismatch("eq point has wrong length"));
+    }
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+    absorb_eq_point(r, transcript);
+    let composition = product_composition(2);
+    composition.absorb(transcript);
+    let subclaim = verify_rounds(
+        stmt.claim_sum,
+        stmt.n_vars,
+        composition.degree(),
+        proof,
+        transcript,
+    )?;
+
+    // Final check: eq(r, ρ) · f(ρ) == final claim
+    let eq = EqOracle::new(r.to_vec());
+    let evals = [eq.query(&subclaim.point)?, oracle.query(&subclaim.point)?];
+    Ok(composition.evaluate(&evals) == subclaim.expected_eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn setup(n_vars: usize) -> (Vec<Fr>, MLPoly<Fr>, Statement<Fr>) {
+        let mut rng = ark_std::test_rng();
+        let r: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly { n_vars, evals };
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.eval_at(&r),
+        };
+        (r, poly, stmt)
+    }
+
+    #[test]
+    fn test_eq_weighted_honest_prover() {
+        for n_vars in [1, 4, 7] {
+            let (r, poly, stmt) = setup(n_vars);
+
+            let mut transcript = Transcript::new(b"eq-test");
+            let out = prove_eq_weighted(&stmt, &r, &poly, &mut transcript);
+
+            let oracle = PolyOracle::new(poly);
+            let mut transcript = Transcript::new(b"eq-test");
+            let result = verify_eq_weighted(&stmt, &r, &out.proof, &oracle, &mut transcript);
+
+            assert!(
+                result.unwrap(),
+                "honest proof for {} vars should verify",
+                n_vars
+            );
+        }
+    }
+
+    #[test]
+    fn test_eq_weighted_matches_product_sumcheck() {
+        let (r, poly, stmt) = setup(6);
+
+        let mut transcript = Transcript::new(b"eq-test");
+        let split = prove_eq_weighted(&stmt, &r, &poly, &mut transcript);
+
+        // Replay the dense product sumcheck of [eq(r, ·), f] at the same challenges
+        let mut eq = MLPoly::eq_table(&r);
+        let mut f = poly;
+        for (round_poly, &rho) in split.proof.round_polys.iter().zip(&split.point) {
+            let dense: Vec<Fr> = (0..3u64)
+                .map(|t| {
+                    let t = Fr::from(t);
+                    let (eq_t, f_t) = (eq.fold_first_var(t), f.fold_first_var(t));
+                    eq_t.evals.iter().zip(&f_t.evals).map(|(a, b)| *a * b).sum()
+                })
+                .collect();
+            assert_eq!(round_poly.evals, dense);
+            eq.fold_first_var_in_place(rho);
+            f.fold_first_var_in_place(rho);
+        }
+        assert_eq!(split.final_evals, vec![eq.evals[0], f.evals[0]]);
+    }
+
+    #[test]
+    fn test_eq_weighted_bound_to_eq_point() {
+        let (r, poly, stmt) = setup(4);
+
+        let mut transcript = Transcript::new(b"eq-test");
+        let out = prove_eq_weighted(&stmt, &r, &poly, &mut transcript);
+
+        // Same statement and proof, claimed for another eq point
+        let mut other = r.clone();
+        other[0] += Fr::from(1u64);
+
+        // The challenges follow r, not just the statement
+        let mut transcript = Transcript::new(b"eq-test");
+        let moved = prove_eq_weighted(&stmt, &other, &poly, &mut transcript);
+        assert_ne!(moved.point, out.point);
+
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"eq-test");
+        let result = verify_eq_weighted(&stmt, &other, &out.proof, &oracle, &mut transcript);
+        assert!(
+            !matches!(result, Ok(true)),
+            "proof for one eq point should not verify for another"
+        );
+    }
+
+    #[test]
+    fn test_eq_weighted_wrong_point_length_is_an_error() {
+        let (r, poly, stmt) = setup(4);
+
+        let mut transcript = Transcript::new(b"eq-test");
+        let out = prove_eq_weighted(&stmt, &r, &poly, &mut transcript);
+
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"eq-test");
+        let result = verify_eq_weighted(&stmt, &r[..3], &out.proof, &oracle, &mut transcript);
+        assert!(matches!(result, Err(Error::DimensionMismatch(_))));
+    }
+
+    #[test]
+    fn test_eq_weighted_wrong_claim_fails() {
+        let (r, poly, mut stmt) = setup(4);
+        stmt.claim_sum += Fr::from(1u64);
+
+        let mut transcript = Transcript::new(b"eq-test");
+        let out = prove_eq_weighted(&stmt, &r, &poly, &mut transcript);
+
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"eq-test");
+        let result = verify_eq_weighted(&stmt, &r, &out.proof, &oracle, &mut transcript);
+
+        assert!(result.is_err(), "wrong claim should fail verification");
+    }
+}
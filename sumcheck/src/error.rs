@@ -9,6 +9,8 @@ pub enum Error {
     TranscriptMismatch(&'static str),
     /// Dimension mismatch (e.g., wrong number of rounds)
     DimensionMismatch(&'static str),
+    /// An oracle answer could not be authenticated (e.g. a bad opening proof)
+    InvalidOpening(&'static str),
 }
 
 impl std::fmt::Display for Error {
@@ -17,6 +19,7 @@ impl std::fmt::Display for Error {
             Error::InvalidProof(msg) => write!(f, "invalid proof: {}", msg),
             Error::TranscriptMismatch(msg) => write!(f, "transcript mismatch: {}", msg),
             Error::DimensionMismatch(msg) => write!(f, "dimension mismatch: {}", msg),
+            Error::InvalidOpening(msg) => write!(f, "invalid opening: {}", msg),
         }
     }
 }
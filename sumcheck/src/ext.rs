@@ -0,0 +1,223 @@
+//! Sumcheck over a small prime field with challenges from an extension field
+//!
+//! Over fields such as Goldilocks (p ≈ 2^64) a single challenge gives only about
+//! 64 − log n bits of soundness, so challenges are drawn from an extension
+//! E ⊇ F instead. The polynomial stays in F for round 1, whose round polynomial
+//! has coefficients in F; binding x_1 to r_1 ∈ E moves the prover to `MLPoly<E>`
+//! for the remaining rounds. The proof, statement and oracle all live in E, so
+//! the ordinary `verify` checks it.
+
+use ark_ff::{Field, PrimeField};
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::protocol::{absorb_statement, append_round_poly, prove_owned, run_rounds};
+use crate::transcript::Transcript;
+use crate::types::{
+    BindingOrder, ProverOutput, RoundPoly, Statement, SumcheckConfig, SumcheckProof,
+};
+
+/// Generate a sumcheck proof for a polynomial over F with challenges in E
+///
+/// # Arguments
+/// * `stmt` - Public statement; the claimed sum is embedded into E
+/// * `poly` - The multilinear polynomial over the base field F
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+/// * `config` - Transcript options and binding order; the verifier must use the
+///   same. With `BindingOrder::HighToLow` the polynomial is lifted to E up front.
+///
+/// # Returns
+/// The proof, the challenge point in E^n and f(r) ∈ E
+///
+/// # Panics
+/// Panics if `poly` does not have `stmt.n_vars` variables
+pub fn prove_ext<F: PrimeField, E: Field<BasePrimeField = F>>(
+    stmt: &Statement<E>,
+    poly: &MLPoly<F>,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> ProverOutput<E> {
+    if config.binding_order == BindingOrder::HighToLow {
+        return prove_owned(stmt, poly.to_ext(), transcript, config);
+    }
+    assert_eq!(
+        poly.n_vars, stmt.n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, config, transcript);
+
+    let mut round_polys = Vec::with_capacity(stmt.n_vars);
+    let mut point = Vec::with_capacity(stmt.n_vars);
+    if stmt.n_vars == 0 {
+        return ProverOutput {
+            proof: SumcheckProof { round_polys },
+            point,
+            final_evals: vec![E::from_base_prime_field(poly.evals[0])],
+        };
+    }
+
+    // Round 1 entirely in F
+    let (g0, g1) = poly.round_sum_g0_g1();
+    let round_poly = RoundPoly::new(E::from_base_prime_field(g0), E::from_base_prime_field(g1));
+    append_round_poly(&round_poly, transcript);
+    round_polys.push(round_poly);
+
+    let r: E = transcript.challenge_scalar(b"r");
+    point.push(r);
+    let mut folded = poly.fold_first_var_ext(r);
+    run_rounds(
+        &mut folded,
+        BindingOrder::LowToHigh,
+        transcript,
+        &mut round_polys,
+        &mut point,
+    );
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![folded.evals[0]],
+    }
+}
+
+/// Oracle for a base-field polynomial queried at points of an extension field
+pub struct ExtOracle<F: PrimeField> {
+    pub poly: MLPoly<F>,
+}
+
+impl<F: PrimeField> ExtOracle<F> {
+    pub fn new(poly: MLPoly<F>) -> Self {
+        Self { poly }
+    }
+}
+
+impl<F: PrimeField, E: Field<BasePrimeField = F>> Oracle<E> for ExtOracle<F> {
+    fn query(&self, x: &[E]) -> Result<E> {
+        if x.len() != self.poly.n_vars {
+            return Err(Error::DimensionMismatch("query point has wrong length"));
+        }
+        if self.poly.n_vars == 0 {
+            return Ok(E::from_base_prime_field(self.poly.evals[0]));
+        }
+        Ok(self.poly.fold_first_var_ext(x[0]).eval_at(&x[1..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::verify_with_config;
+    use ark_std::UniformRand;
+    use goldilocks::{Goldilocks, Goldilocks2};
+
+    // The MontConfig derive expands into a function-local impl
+    #[allow(non_local_definitions)]
+    mod goldilocks {
+        use ark_ff::fields::{Fp2, Fp2Config, Fp64, MontBackend, MontConfig};
+        use ark_ff::MontFp;
+
+        /// Goldilocks: p = 2^64 - 2^32 + 1
+        #[derive(MontConfig)]
+        #[modulus = "18446744069414584321"]
+        #[generator = "7"]
+        pub struct GoldilocksConfig;
+        pub type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+        /// Goldilocks[X] / (X^2 - 7); 7 is a quadratic non-residue
+        pub struct Goldilocks2Config;
+        impl Fp2Config for Goldilocks2Config {
+            type Fp = Goldilocks;
+            const NONRESIDUE: Goldilocks = MontFp!("7");
+            const FROBENIUS_COEFF_FP2_C1: &'static [Goldilocks] =
+                &[MontFp!("1"), MontFp!("18446744069414584320")];
+        }
+        pub type Goldilocks2 = Fp2<Goldilocks2Config>;
+    }
+
+    fn setup(n_vars: usize) -> (MLPoly<Goldilocks>, Statement<Goldilocks2>) {
+        let mut rng = ark_std::test_rng();
+        let poly = MLPoly::from_evals(
+            (0..1 << n_vars)
+                .map(|_| Goldilocks::rand(&mut rng))
+                .collect(),
+        );
+        let stmt = Statement {
+            n_vars,
+            claim_sum: Goldilocks2::from_base_prime_field(poly.sum_all()),
+        };
+        (poly, stmt)
+    }
+
+    #[test]
+    fn test_ext_sumcheck_honest_prover() {
+        let config = SumcheckConfig::default();
+        for n_vars in [0, 1, 6] {
+            let (poly, stmt) = setup(n_vars);
+
+            let mut transcript = Transcript::new(b"ext-test");
+            let out = prove_ext(&stmt, &poly, &mut transcript, &config);
+            // Challenges really leave the base field
+            assert!(out.point.iter().all(|r| r.c1 != Goldilocks::from(0u64)));
+
+            let oracle = ExtOracle::new(poly);
+            let mut transcript = Transcript::new(b"ext-test");
+            let result = verify_with_config(&stmt, &out.proof, &oracle, &mut transcript, &config);
+            assert!(
+                result.unwrap(),
+                "honest proof for {} vars should verify",
+                n_vars
+            );
+        }
+    }
+
+    #[test]
+    fn test_ext_prover_matches_lifted_polynomial() {
+        let (poly, stmt) = setup(5);
+        let config = SumcheckConfig::default();
+
+        let mut transcript = Transcript::new(b"ext-test");
+        let out = prove_ext(&stmt, &poly, &mut transcript, &config);
+
+        let lifted: MLPoly<Goldilocks2> = poly.to_ext();
+        let mut transcript = Transcript::new(b"ext-test");
+        let reference =
+            crate::protocol::prove_with_output(&stmt, &lifted, &mut transcript, &config);
+
+        for (a, b) in out
+            .proof
+            .round_polys
+            .iter()
+            .zip(&reference.proof.round_polys)
+        {
+            assert_eq!(a.evals, b.evals);
+        }
+        assert_eq!(out.point, reference.point);
+        assert_eq!(out.final_evals, reference.final_evals);
+    }
+
+    #[test]
+    fn test_ext_wrong_claim_fails() {
+        let (poly, mut stmt) = setup(4);
+        stmt.claim_sum += Goldilocks2::from(1u64);
+        let config = SumcheckConfig::default();
+
+        let mut transcript = Transcript::new(b"ext-test");
+        let out = prove_ext(&stmt, &poly, &mut transcript, &config);
+
+        let oracle = ExtOracle::new(poly);
+        let mut transcript = Transcript::new(b"ext-test");
+        let result = verify_with_config(&stmt, &out.proof, &oracle, &mut transcript, &config);
+        assert!(result.is_err(), "wrong claim should fail verification");
+    }
+
+    #[test]
+    fn test_ext_oracle_rejects_wrong_length() {
+        let (poly, _) = setup(3);
+        let oracle = ExtOracle::new(poly);
+        let x = vec![Goldilocks2::from(2u64); 2];
+        let result: Result<Goldilocks2> = oracle.query(&x);
+        assert!(matches!(result, Err(Error::DimensionMismatch(_))));
+        assert!(Oracle::<Goldilocks2>::query(&oracle, &[]).is_err());
+    }
+}
@@ -0,0 +1,612 @@
+//! GKR protocol for layered arithmetic circuits
+//!
+//! A circuit is a stack of gate layers over an input layer. Every gate adds or
+//! multiplies two values of the layer below it. Writing V_i for the MLE of the
+//! values on level i (padded with zeros to a power of two), the wiring
+//! predicates add_i and mul_i give
+//!
+//! V_i(z) = ∑_{x, y} add_i(z, x, y) · (V_{i-1}(x) + V_{i-1}(y)) + mul_i(z, x, y) · V_{i-1}(x) · V_{i-1}(y).
+//!
+//! Starting from a random point of the output MLE, each layer turns a claim on
+//! V_i into a claim on V_{i-1}:
+//! 1. Two degree-2 sumchecks, first over x and then over y (the linear-time
+//!    prover of Libra), end in claims on V_{i-1}(x) and V_{i-1}(y).
+//! 2. The prover sends q(t) = V_{i-1}(ℓ(t)) on the line ℓ through x and y; a
+//!    random τ leaves the single claim V_{i-1}(ℓ(τ)) = q(τ).
+//!
+//! The last claim is on the input MLE and is checked against a `CommittedOracle`.
+//!
+//! Fiat-Shamir: every challenge depends on the circuit, a commitment to the
+//! input MLE and the outputs, which both sides absorb first. The verifier
+//! absorbs the commitment of the very oracle it opens, so a prover cannot pick
+//! inputs after the last challenge that match only the final claim.
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use mlpoly::{MLPoly, SparseMLPoly};
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::pcs::{CommittedOracle, PolynomialCommitment};
+use crate::protocol::append_round_poly;
+use crate::transcript::Transcript;
+use crate::types::{GkrLayerProof, GkrProof, GkrProverOutput, RoundPoly, Statement};
+use crate::virtual_poly::{prove_virtual, verify_virtual_rounds, Composition, VirtualPoly};
+
+/// Operation of a gate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GateType {
+    Add,
+    Mul,
+}
+
+/// Fan-in-two gate reading two values of the level below
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub op: GateType,
+    /// Index of the left input in the level below
+    pub left: usize,
+    /// Index of the right input in the level below
+    pub right: usize,
+}
+
+impl Gate {
+    pub fn add(left: usize, right: usize) -> Self {
+        Self {
+            op: GateType::Add,
+            left,
+            right,
+        }
+    }
+
+    pub fn mul(left: usize, right: usize) -> Self {
+        Self {
+            op: GateType::Mul,
+            left,
+            right,
+        }
+    }
+}
+
+/// Layered arithmetic circuit
+///
+/// Level 0 holds the inputs; level i ≥ 1 holds the gates `layers[i - 1]`,
+/// which read from level i - 1. The last level is the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Circuit {
+    /// Number of inputs
+    pub num_inputs: usize,
+    /// Gate layers from the input side to the output
+    pub layers: Vec<Vec<Gate>>,
+}
+
+impl Circuit {
+    /// Create a circuit with no gates yet
+    ///
+    /// # Panics
+    /// Panics if `num_inputs` is zero
+    pub fn new(num_inputs: usize) -> Self {
+        assert!(num_inputs > 0, "circuit needs at least one input");
+        Self {
+            num_inputs,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Add a gate layer on top, reading from the current top level
+    ///
+    /// # Panics
+    /// Panics if `gates` is empty or a gate reads past the end of the level below
+    pub fn add_layer(&mut self, gates: Vec<Gate>) {
+        assert!(!gates.is_empty(), "gate layer is empty");
+        let below = self.width(self.depth());
+        assert!(
+            gates.iter().all(|g| g.left < below && g.right < below),
+            "gate input out of range for a level of {} values",
+            below
+        );
+        self.layers.push(gates);
+    }
+
+    /// Number of gate layers
+    pub fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Number of values on a level
+    pub fn width(&self, level: usize) -> usize {
+        match level {
+            0 => self.num_inputs,
+            _ => self.layers[level - 1].len(),
+        }
+    }
+
+    /// Number of variables of the MLE of a level
+    pub fn num_vars(&self, level: usize) -> usize {
+        self.width(level).next_power_of_two().trailing_zeros() as usize
+    }
+
+    /// Values on every level, zero-padded to a power of two
+    ///
+    /// # Panics
+    /// Panics if `inputs` does not have `num_inputs` entries
+    pub fn evaluate<F: PrimeField>(&self, inputs: &[F]) -> Vec<MLPoly<F>> {
+        assert_eq!(inputs.len(), self.num_inputs, "wrong number of inputs");
+        let mut input = MLPoly::zero(self.num_vars(0));
+        input.evals[..inputs.len()].copy_from_slice(inputs);
+
+        let mut values = vec![input];
+        for (i, gates) in self.layers.iter().enumerate() {
+            let below = &values[i].evals;
+            let mut level = MLPoly::zero(self.num_vars(i + 1));
+            for (v, g) in level.evals.iter_mut().zip(gates) {
+                *v = match g.op {
+                    GateType::Add => below[g.left] + below[g.right],
+                    GateType::Mul => below[g.left] * below[g.right],
+                };
+            }
+            values.push(level);
+        }
+        values
+    }
+
+    /// Wiring predicates (add_i, mul_i) of level i ≥ 1 as sparse MLEs
+    ///
+    /// Variables are (z, x, y): the gate index z of level i in the low bits,
+    /// then its left input x and right input y on level i - 1.
+    pub fn wiring<F: PrimeField>(&self, level: usize) -> (SparseMLPoly<F>, SparseMLPoly<F>) {
+        assert!(
+            level >= 1 && level <= self.depth(),
+            "level {} has no gates",
+            level
+        );
+        let s_out = self.num_vars(level);
+        let s_in = self.num_vars(level - 1);
+        let n_vars = s_out + 2 * s_in;
+
+        let mut add = Vec::new();
+        let mut mul = Vec::new();
+        for (z, g) in self.layers[level - 1].iter().enumerate() {
+            let index = z | (g.left << s_out) | (g.right << (s_out + s_in));
+            match g.op {
+                GateType::Add => add.push((index, F::ONE)),
+                GateType::Mul => mul.push((index, F::ONE)),
+            }
+        }
+        (
+            SparseMLPoly::new(n_vars, add),
+            SparseMLPoly::new(n_vars, mul),
+        )
+    }
+
+    /// Absorb the circuit shape and the input commitment so that challenges depend on them
+    fn absorb<C: CanonicalSerialize>(&self, commitment: &C, transcript: &mut Transcript) {
+        transcript.append_message(b"protocol", b"gkr");
+        transcript.append_u64(b"num_inputs", self.num_inputs as u64);
+        transcript.append_u64(b"depth", self.depth() as u64);
+        for gates in &self.layers {
+            transcript.append_u64(b"width", gates.len() as u64);
+            for g in gates {
+                let op = match g.op {
+                    GateType::Add => 0,
+                    GateType::Mul => 1,
+                };
+                transcript.append_u64(b"op", op);
+                transcript.append_u64(b"left", g.left as u64);
+                transcript.append_u64(b"right", g.right as u64);
+            }
+        }
+        transcript.append_serializable(b"input_commitment", commitment);
+    }
+}
+
+/// V · A + B, the shape of both sumchecks of a layer
+fn layer_composition<F: PrimeField>() -> Composition<F> {
+    Composition {
+        num_mles: 3,
+        terms: vec![(F::ONE, vec![0, 1]), (F::ONE, vec![2])],
+    }
+}
+
+/// ℓ(t) = x + t · (y - x)
+fn line_point<F: PrimeField>(x: &[F], y: &[F], t: F) -> Vec<F> {
+    x.iter().zip(y).map(|(&a, &b)| a + t * (b - a)).collect()
+}
+
+/// Absorb the outputs and draw the random point of the output MLE
+fn output_point<F: PrimeField>(outputs: &MLPoly<F>, transcript: &mut Transcript) -> Vec<F> {
+    for v in &outputs.evals {
+        transcript.append_field(b"output", v);
+    }
+    (0..outputs.n_vars)
+        .map(|_| transcript.challenge_scalar(b"r"))
+        .collect()
+}
+
+/// Reduce the claim V_i(point) = claim to a claim on V_{i-1}
+///
+/// `below` is V_{i-1}; returns the layer proof, the new point and the new claim.
+fn prove_layer<F: PrimeField>(
+    gates: &[Gate],
+    below: &MLPoly<F>,
+    point: &[F],
+    claim: F,
+    transcript: &mut Transcript,
+) -> (GkrLayerProof<F>, Vec<F>, F) {
+    let s = below.n_vars;
+    let v = &below.evals;
+    let eq_z = MLPoly::eq_table(point);
+
+    // Phase 1: ∑_y f(x, y) = V(x) · h1(x) + h2(x) for boolean x, hence for all x
+    let mut h1 = MLPoly::zero(s);
+    let mut h2 = MLPoly::zero(s);
+    for (z, g) in gates.iter().enumerate() {
+        let w = eq_z.evals[z];
+        match g.op {
+            GateType::Add => {
+                h1.evals[g.left] += w;
+                h2.evals[g.left] += w * v[g.right];
+            }
+            GateType::Mul => h1.evals[g.left] += w * v[g.right],
+        }
+    }
+    let stmt = Statement {
+        n_vars: s,
+        claim_sum: claim,
+    };
+    let poly = VirtualPoly {
+        n_vars: s,
+        composition: layer_composition(),
+        mles: vec![below, &h1, &h2],
+    };
+    let phase1 = prove_virtual(&stmt, &poly, transcript);
+    let x = phase1.point;
+    let v_x = phase1.final_evals[0];
+    let claim = v_x * phase1.final_evals[1] + phase1.final_evals[2];
+
+    // Phase 2: f(x, y) = V(y) · g1(y) + g2(y) for boolean y
+    let eq_x = MLPoly::eq_table(&x);
+    let mut g1 = MLPoly::zero(s);
+    let mut g2 = MLPoly::zero(s);
+    for (z, g) in gates.iter().enumerate() {
+        let w = eq_z.evals[z] * eq_x.evals[g.left];
+        match g.op {
+            GateType::Add => {
+                g1.evals[g.right] += w;
+                g2.evals[g.right] += w * v_x;
+            }
+            GateType::Mul => g1.evals[g.right] += w * v_x,
+        }
+    }
+    let stmt = Statement {
+        n_vars: s,
+        claim_sum: claim,
+    };
+    let poly = VirtualPoly {
+        n_vars: s,
+        composition: layer_composition(),
+        mles: vec![below, &g1, &g2],
+    };
+    let phase2 = prove_virtual(&stmt, &poly, transcript);
+    let y = phase2.point;
+
+    // Two claims to one: V restricted to the line through x and y has degree s
+    let line = RoundPoly::from_evals(
+        (0..=s.max(1))
+            .map(|t| below.eval_at(&line_point(&x, &y, F::from(t as u64))))
+            .collect(),
+    );
+    append_round_poly(&line, transcript);
+    let tau: F = transcript.challenge_scalar(b"tau");
+    let point = line_point(&x, &y, tau);
+    let claim = line.eval(tau);
+
+    let proof = GkrLayerProof {
+        phase1: phase1.proof,
+        phase2: phase2.proof,
+        line,
+    };
+    (proof, point, claim)
+}
+
+/// Evaluate the circuit and prove its outputs
+///
+/// # Arguments
+/// * `circuit` - The layered circuit, known to the verifier
+/// * `inputs` - Values of the input gates
+/// * `commitment` - Commitment to the input MLE, bound before any challenge
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The proof, the output values, and the point and value of the final claim on
+/// the input MLE (what a commitment to the inputs must be opened at)
+///
+/// # Panics
+/// Panics if `inputs` does not have `circuit.num_inputs` entries
+pub fn prove_gkr<F: PrimeField, C: CanonicalSerialize>(
+    circuit: &Circuit,
+    inputs: &[F],
+    commitment: &C,
+    transcript: &mut Transcript,
+) -> GkrProverOutput<F> {
+    let values = circuit.evaluate(inputs);
+    let depth = circuit.depth();
+    circuit.absorb(commitment, transcript);
+
+    let mut point = output_point(&values[depth], transcript);
+    let mut claim = values[depth].eval_at(&point);
+    let mut layers = Vec::with_capacity(depth);
+    for level in (1..=depth).rev() {
+        let (proof, next_point, next_claim) = prove_layer(
+            &circuit.layers[level - 1],
+            &values[level - 1],
+            &point,
+            claim,
+            transcript,
+        );
+        layers.push(proof);
+        point = next_point;
+        claim = next_claim;
+    }
+
+    GkrProverOutput {
+        proof: GkrProof { layers },
+        outputs: values[depth].evals[..circuit.width(depth)].to_vec(),
+        input_point: point,
+        input_eval: claim,
+    }
+}
+
+/// Verify a GKR proof that `circuit` maps the committed inputs to `outputs`
+///
+/// # Arguments
+/// * `circuit` - The layered circuit, as used by the prover
+/// * `outputs` - Claimed values of the output gates
+/// * `proof` - The GKR proof to verify
+/// * `input_oracle` - Committed MLE of the inputs (zero-padded to a power of two),
+///   opened at the prover's `input_point`; its commitment is absorbed first
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final input oracle check fails
+/// * `Err(_)` if the shapes disagree, a round check fails or a layer's wiring check fails
+pub fn verify_gkr<F: PrimeField, P: PolynomialCommitment<F>>(
+    circuit: &Circuit,
+    outputs: &[F],
+    proof: &GkrProof<F>,
+    input_oracle: &CommittedOracle<'_, F, P>,
+    transcript: &mut Transcript,
+) -> Result<bool>
+where
+    P::Commitment: CanonicalSerialize,
+{
+    let depth = circuit.depth();
+    if outputs.len() != circuit.width(depth) {
+        return Err(Error::DimensionMismatch("wrong number of outputs"));
+    }
+    if proof.layers.len() != depth {
+        return Err(Error::DimensionMismatch("wrong number of layer proofs"));
+    }
+    circuit.absorb(&input_oracle.commitment, transcript);
+
+    let mut output = MLPoly::zero(circuit.num_vars(depth));
+    output.evals[..outputs.len()].copy_from_slice(outputs);
+    let mut point = output_point(&output, transcript);
+    let mut claim = output.eval_at(&point);
+
+    let composition = layer_composition::<F>();
+    for (level, layer) in (1..=depth).rev().zip(&proof.layers) {
+        let s = circuit.num_vars(level - 1);
+        if layer.line.evals.len() != s.max(1) + 1 {
+            return Err(Error::DimensionMismatch("wrong line polynomial degree"));
+        }
+
+        let mut subclaims = Vec::with_capacity(2);
+        for phase in [&layer.phase1, &layer.phase2] {
+            let stmt = Statement {
+                n_vars: s,
+                claim_sum: claim,
+            };
+            let subclaim = verify_virtual_rounds(&stmt, &composition, phase, transcript)?;
+            claim = subclaim.expected_eval;
+            subclaims.push(subclaim.point);
+        }
+        let (x, y) = (&subclaims[0], &subclaims[1]);
+
+        // f(z, x, y) with V(x) = q(0) and V(y) = q(1) from the line polynomial
+        let (v_x, v_y) = (layer.line.eval_0(), layer.line.eval_1());
+        let (add, mul) = circuit.wiring::<F>(level);
+        let zxy: Vec<F> = point.iter().chain(x).chain(y).copied().collect();
+        let expected = add.eval_at(&zxy) * (v_x + v_y) + mul.eval_at(&zxy) * v_x * v_y;
+        if expected != claim {
+            return Err(Error::InvalidProof("gkr layer check failed"));
+        }
+
+        append_round_poly(&layer.line, transcript);
+        let tau: F = transcript.challenge_scalar(b"tau");
+        point = line_point(x, y, tau);
+        claim = layer.line.eval(tau);
+    }
+
+    // Final check: input MLE at the last point == final claim
+    Ok(input_oracle.query(&point)? == claim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcs::kzg::{KzgProverKey, KzgVerifierKey, MultilinearKzg};
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::Field;
+    use ark_std::rand::Rng;
+    use ark_std::UniformRand;
+
+    /// 5 inputs -> 3 gates -> 2 gates -> 1 output, with padding on every level
+    fn small_circuit() -> Circuit {
+        let mut circuit = Circuit::new(5);
+        circuit.add_layer(vec![Gate::mul(0, 1), Gate::add(2, 3), Gate::mul(4, 4)]);
+        circuit.add_layer(vec![Gate::add(0, 1), Gate::mul(1, 2)]);
+        circuit.add_layer(vec![Gate::mul(0, 1)]);
+        circuit
+    }
+
+    fn random_circuit(num_inputs: usize, widths: &[usize], rng: &mut impl Rng) -> Circuit {
+        let mut circuit = Circuit::new(num_inputs);
+        for &width in widths {
+            let below = circuit.width(circuit.depth());
+            let gates = (0..width)
+                .map(|_| {
+                    let (l, r) = (rng.gen_range(0..below), rng.gen_range(0..below));
+                    if rng.gen() {
+                        Gate::add(l, r)
+                    } else {
+                        Gate::mul(l, r)
+                    }
+                })
+                .collect();
+            circuit.add_layer(gates);
+        }
+        circuit
+    }
+
+    type Kzg = MultilinearKzg<Bn254>;
+
+    fn input_poly(circuit: &Circuit, inputs: &[Fr]) -> MLPoly<Fr> {
+        circuit.evaluate(inputs).swap_remove(0)
+    }
+
+    fn keys(circuit: &Circuit) -> (KzgProverKey<Bn254>, KzgVerifierKey<Bn254>) {
+        Kzg::setup(circuit.num_vars(0), &mut ark_std::test_rng())
+    }
+
+    /// Prove with the commitment to `committed` bound in the transcript
+    fn prove(circuit: &Circuit, inputs: &[Fr], committed: &MLPoly<Fr>) -> GkrProverOutput<Fr> {
+        let (pk, _) = keys(circuit);
+        let mut transcript = Transcript::new(b"gkr-test");
+        prove_gkr(
+            circuit,
+            inputs,
+            &Kzg::commit(&pk, committed),
+            &mut transcript,
+        )
+    }
+
+    /// Verify `proof` for `outputs`, opening `committed` at `input_point`
+    fn verify(
+        circuit: &Circuit,
+        outputs: &[Fr],
+        proof: &GkrProof<Fr>,
+        input_point: &[Fr],
+        committed: &MLPoly<Fr>,
+    ) -> Result<bool> {
+        let (pk, vk) = keys(circuit);
+        let (value, opening) = Kzg::open(&pk, committed, input_point);
+        let commitment = Kzg::commit(&pk, committed);
+        let oracle =
+            CommittedOracle::<Fr, Kzg>::new(&vk, commitment, input_point.to_vec(), value, opening);
+        let mut transcript = Transcript::new(b"gkr-test");
+        verify_gkr(circuit, outputs, proof, &oracle, &mut transcript)
+    }
+
+    #[test]
+    fn test_gkr_honest_prover() {
+        let circuit = small_circuit();
+        let inputs: Vec<Fr> = [2u64, 3, 4, 5, 6].iter().map(|&v| Fr::from(v)).collect();
+        let poly = input_poly(&circuit, &inputs);
+
+        let out = prove(&circuit, &inputs, &poly);
+        // ((2·3) + (4+5)) · ((4+5) · 6·6) = 15 · 324
+        assert_eq!(out.outputs, vec![Fr::from(4860u64)]);
+        assert_eq!(poly.eval_at(&out.input_point), out.input_eval);
+
+        let result = verify(&circuit, &out.outputs, &out.proof, &out.input_point, &poly);
+        assert!(result.unwrap(), "honest GKR proof should verify");
+    }
+
+    #[test]
+    fn test_gkr_random_circuits() {
+        let mut rng = ark_std::test_rng();
+        for (num_inputs, widths) in [(8, vec![8, 4, 2]), (13, vec![16, 7, 7, 3]), (1, vec![1])] {
+            let circuit = random_circuit(num_inputs, &widths, &mut rng);
+            let inputs: Vec<Fr> = (0..num_inputs).map(|_| Fr::rand(&mut rng)).collect();
+            let poly = input_poly(&circuit, &inputs);
+
+            let out = prove(&circuit, &inputs, &poly);
+            let expected = &circuit.evaluate(&inputs)[circuit.depth()];
+            assert_eq!(out.outputs, expected.evals[..widths[widths.len() - 1]]);
+
+            let result = verify(&circuit, &out.outputs, &out.proof, &out.input_point, &poly);
+            assert!(
+                result.unwrap(),
+                "honest proof for widths {:?} should verify",
+                widths
+            );
+        }
+    }
+
+    #[test]
+    fn test_gkr_wrong_output_fails() {
+        let circuit = small_circuit();
+        let inputs: Vec<Fr> = (1..=5u64).map(Fr::from).collect();
+        let poly = input_poly(&circuit, &inputs);
+        let out = prove(&circuit, &inputs, &poly);
+
+        let wrong = vec![out.outputs[0] + Fr::from(1u64)];
+        let result = verify(&circuit, &wrong, &out.proof, &out.input_point, &poly);
+        assert!(
+            !matches!(result, Ok(true)),
+            "wrong output should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_gkr_wrong_inputs_or_tampered_line_fail() {
+        let circuit = small_circuit();
+        let inputs: Vec<Fr> = (1..=5u64).map(Fr::from).collect();
+        let poly = input_poly(&circuit, &inputs);
+
+        // Proof is consistent, but the committed inputs differ
+        let mut other = inputs.clone();
+        other[4] += Fr::from(1u64);
+        let other = input_poly(&circuit, &other);
+        let out = prove(&circuit, &inputs, &other);
+        let result = verify(&circuit, &out.outputs, &out.proof, &out.input_point, &other);
+        assert!(
+            !result.unwrap(),
+            "proof should not verify against other inputs"
+        );
+
+        let out = prove(&circuit, &inputs, &poly);
+        let mut tampered = out.proof.clone();
+        tampered.layers[1].line.evals[2] += Fr::from(1u64);
+        let result = verify(&circuit, &out.outputs, &tampered, &out.input_point, &poly);
+        assert!(
+            !matches!(result, Ok(true)),
+            "tampered line should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_gkr_inputs_chosen_after_challenges_rejected() {
+        let circuit = small_circuit();
+        let inputs: Vec<Fr> = (1..=5u64).map(Fr::from).collect();
+        let poly = input_poly(&circuit, &inputs);
+        let out = prove(&circuit, &inputs, &poly);
+
+        // Inputs that differ from `inputs` but whose MLE agrees with it at the
+        // final point; their commitment is not the one bound in the transcript
+        let eq = MLPoly::eq_table(&out.input_point);
+        let mut other = inputs.clone();
+        other[0] += Fr::from(1u64);
+        other[1] -= eq.evals[0] * eq.evals[1].inverse().unwrap();
+        let other = input_poly(&circuit, &other);
+        assert_eq!(other.eval_at(&out.input_point), out.input_eval);
+
+        let result = verify(&circuit, &out.outputs, &out.proof, &out.input_point, &other);
+        assert!(
+            !matches!(result, Ok(true)),
+            "inputs chosen after the challenges should be rejected"
+        );
+    }
+}
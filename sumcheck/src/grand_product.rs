@@ -0,0 +1,216 @@
+//! Grand product argument
+//!
+//! Proves ∏_{x ∈ {0,1}^n} f(x) = P for one or more polynomials f of the same
+//! size. Each product is the root of a binary tree whose level i holds the
+//! pairwise products of level i + 1 (`tree`); the verifier checks the roots
+//! against the claimed products and is left with one evaluation claim per f at
+//! a shared point.
+//!
+//! Fiat-Shamir: `prove_grand_product` / `verify_grand_product` take one
+//! commitment per f and absorb them, with the shape, before any challenge.
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::transcript::Transcript;
+use crate::tree::{prove_tree, verify_tree};
+use crate::types::{GrandProductClaims, GrandProductProof, GrandProductProverOutput};
+use crate::virtual_poly::Composition;
+
+/// v_j = v_{j,0} · v_{j,1} for each of k trees, over the halves [v_{1,0}, v_{1,1}, ...]
+fn product_rule<F: PrimeField>(k: usize) -> Vec<Composition<F>> {
+    (0..k)
+        .map(|j| Composition {
+            num_mles: 2 * k,
+            terms: vec![(F::ONE, vec![2 * j, 2 * j + 1])],
+        })
+        .collect()
+}
+
+fn absorb_shape(n_vars: usize, num_polys: usize, transcript: &mut Transcript) {
+    transcript.append_message(b"protocol", b"grand_product");
+    transcript.append_u64(b"n_vars", n_vars as u64);
+    transcript.append_u64(b"num_polys", num_polys as u64);
+}
+
+/// Prove the products of all evaluations of each polynomial
+///
+/// # Arguments
+/// * `polys` - Polynomials f_1, ..., f_k, all with the same number of variables
+/// * `commitments` - Commitments to f_1, ..., f_k, bound before any challenge
+/// * `transcript` - Fiat-Shamir transcript
+///
+/// # Returns
+/// The proof (whose roots are the products), and the point and values of the
+/// final claims on f_1, ..., f_k
+///
+/// # Panics
+/// Panics if `polys` is empty, the polynomials differ in size or there is not
+/// one commitment per polynomial
+pub fn prove_grand_product<F: PrimeField, C: CanonicalSerialize>(
+    polys: Vec<MLPoly<F>>,
+    commitments: &[C],
+    transcript: &mut Transcript,
+) -> GrandProductProverOutput<F> {
+    assert_eq!(
+        commitments.len(),
+        polys.len(),
+        "need one commitment per polynomial"
+    );
+    transcript.append_commitments(commitments);
+    prove_products(polys, transcript)
+}
+
+/// `prove_grand_product` for leaves derived from already bound polynomials
+pub(crate) fn prove_products<F: PrimeField>(
+    polys: Vec<MLPoly<F>>,
+    transcript: &mut Transcript,
+) -> GrandProductProverOutput<F> {
+    assert!(!polys.is_empty(), "need at least one polynomial");
+    absorb_shape(polys[0].n_vars, polys.len(), transcript);
+    let (tree, point, evals) = prove_tree(&product_rule(polys.len()), polys, transcript);
+    GrandProductProverOutput {
+        proof: GrandProductProof { tree },
+        claims: GrandProductClaims { point, evals },
+    }
+}
+
+/// Verify that each polynomial multiplies out to the matching entry of `products`
+///
+/// # Arguments
+/// * `n_vars` - Number of variables of every polynomial
+/// * `products` - Claimed products P_1, ..., P_k
+/// * `commitments` - Commitments to f_1, ..., f_k, as given to the prover
+/// * `proof` - The grand product proof to verify
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(claims)` if the proof checks out; the caller must check each claimed
+///   evaluation against the commitment it was bound to
+/// * `Err(_)` if the counts differ, a product differs from the proof or a tree
+///   layer fails
+pub fn verify_grand_product<F: PrimeField, C: CanonicalSerialize>(
+    n_vars: usize,
+    products: &[F],
+    commitments: &[C],
+    proof: &GrandProductProof<F>,
+    transcript: &mut Transcript,
+) -> Result<GrandProductClaims<F>> {
+    if commitments.len() != products.len() {
+        return Err(Error::DimensionMismatch("need one commitment per product"));
+    }
+    transcript.append_commitments(commitments);
+    verify_products(n_vars, products, proof, transcript)
+}
+
+/// `verify_grand_product` for leaves derived from already bound polynomials
+pub(crate) fn verify_products<F: PrimeField>(
+    n_vars: usize,
+    products: &[F],
+    proof: &GrandProductProof<F>,
+    transcript: &mut Transcript,
+) -> Result<GrandProductClaims<F>> {
+    if products.is_empty() {
+        return Err(Error::DimensionMismatch("need at least one product"));
+    }
+    absorb_shape(n_vars, products.len(), transcript);
+    let (point, evals) = verify_tree(
+        &product_rule(products.len()),
+        n_vars,
+        &proof.tree,
+        transcript,
+    )?;
+    if proof.tree.roots != products {
+        return Err(Error::InvalidProof("grand product mismatch"));
+    }
+    Ok(GrandProductClaims { point, evals })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize) -> MLPoly<Fr> {
+        let mut rng = ark_std::test_rng();
+        MLPoly::from_evals((0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect())
+    }
+
+    #[test]
+    fn test_grand_product_honest_prover() {
+        for n_vars in [0, 3, 6] {
+            let f = random_poly(n_vars);
+            let g = MLPoly::from_evals(f.evals.iter().map(|v| v * v).collect());
+            let products: Vec<Fr> = [&f, &g].iter().map(|p| p.evals.iter().product()).collect();
+
+            let commitments = [f.commit_merkle().root(), g.commit_merkle().root()];
+
+            let mut transcript = Transcript::new(b"grand-product-test");
+            let out = prove_grand_product(vec![f.clone(), g.clone()], &commitments, &mut transcript);
+            assert_eq!(out.proof.products(), products.as_slice());
+
+            let mut transcript = Transcript::new(b"grand-product-test");
+            let claims =
+                verify_grand_product(n_vars, &products, &commitments, &out.proof, &mut transcript)
+                    .unwrap();
+            assert_eq!(claims, out.claims);
+            assert_eq!(
+                claims.evals,
+                vec![f.eval_at(&claims.point), g.eval_at(&claims.point)]
+            );
+        }
+    }
+
+    #[test]
+    fn test_grand_product_wrong_product_fails() {
+        let f = random_poly(4);
+        let product: Fr = f.evals.iter().product();
+        let commitments = [f.commit_merkle().root()];
+        let mut transcript = Transcript::new(b"grand-product-test");
+        let out = prove_grand_product(vec![f], &commitments, &mut transcript);
+
+        let mut transcript = Transcript::new(b"grand-product-test");
+        let wrong = [product + Fr::from(1u64)];
+        assert!(
+            verify_grand_product(4, &wrong, &commitments, &out.proof, &mut transcript).is_err()
+        );
+    }
+
+    #[test]
+    fn test_grand_product_commitment_is_bound() {
+        let f = random_poly(4);
+        let product: Fr = f.evals.iter().product();
+        let mut transcript = Transcript::new(b"grand-product-test");
+        let out = prove_grand_product(vec![f.clone()], &[f.commit_merkle().root()], &mut transcript);
+
+        // The challenges follow the commitment, so another one breaks the proof
+        let mut g = f;
+        g.evals[0] += Fr::from(1u64);
+        let other = [g.commit_merkle().root()];
+        let mut transcript = Transcript::new(b"grand-product-test");
+        assert!(verify_grand_product(4, &[product], &other, &out.proof, &mut transcript).is_err());
+    }
+
+    #[test]
+    fn test_grand_product_wrong_eval_claim() {
+        // A prover committed to f but proving the product of a different f'
+        // passes the tree, but its leaf claim does not match f
+        let f = random_poly(5);
+        let mut other = f.clone();
+        other.evals.swap(0, 1);
+        other.evals[2] += Fr::from(1u64);
+        let product: Fr = other.evals.iter().product();
+        let commitments = [f.commit_merkle().root()];
+
+        let mut transcript = Transcript::new(b"grand-product-test");
+        let out = prove_grand_product(vec![other], &commitments, &mut transcript);
+        let mut transcript = Transcript::new(b"grand-product-test");
+        let claims =
+            verify_grand_product(5, &[product], &commitments, &out.proof, &mut transcript)
+                .unwrap();
+        assert_ne!(claims.evals[0], f.eval_at(&claims.point));
+    }
+}
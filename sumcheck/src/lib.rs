@@ -1,6 +1,11 @@
 //! Sumcheck protocol implementation
 //!
 //! This crate provides a non-interactive sumcheck protocol using Fiat-Shamir transform.
+//! By default the statement is absorbed into the transcript before round 1
+//! (see [`SumcheckConfig`]).
+//!
+//! The `parallel` feature runs the prover's per-round sums and folds on rayon.
+//! Proofs are bit-identical to the serial build.
 //!
 //! # Example
 //!
@@ -27,10 +32,50 @@ pub mod types;
 pub mod oracle;
 pub mod transcript;
 pub mod protocol;
+pub mod product;
+pub mod virtual_poly;
+pub mod zerocheck;
+pub mod eq_sumcheck;
+pub mod pcs;
+pub mod batched;
+pub mod streaming;
+pub mod ext;
+pub mod gkr;
+pub mod spartan;
+pub mod tree;
+pub mod logup;
+pub mod grand_product;
+pub mod permutation;
 
 // Re-export main types for convenience
 pub use error::{Error, Result};
-pub use types::{Statement, RoundPoly, SumcheckProof};
-pub use oracle::{Oracle, PolyOracle};
+pub use types::{
+    BatchedProof, BindingOrder, GkrLayerProof, GkrProof, GkrProverOutput, GrandProductClaims,
+    GrandProductProof, GrandProductProverOutput, LogUpClaims, LogUpProof, LogUpProverOutput,
+    MultisetClaims, MultisetProverOutput, PermutationClaims, PermutationProof,
+    PermutationProverOutput, ProverOutput, RoundPoly, SpartanProof, SpartanProverOutput,
+    Statement, SubClaim, SumcheckConfig, SumcheckProof, TreeLayerProof, TreeProof,
+    STATEMENT_DOMAIN,
+};
+pub use oracle::{EqOracle, Oracle, PolyOracle};
 pub use transcript::Transcript;
-pub use protocol::{prove, verify};
+pub use protocol::{
+    prove, prove_in_place, prove_owned, prove_small, prove_with_config,
+    prove_with_output, verify, verify_rounds, verify_with_config,
+};
+pub use product::{prove_product, verify_product};
+pub use virtual_poly::{prove_virtual, verify_virtual, Composition, VirtualPoly};
+pub use zerocheck::{prove_zerocheck, verify_zerocheck};
+pub use eq_sumcheck::{prove_eq_weighted, verify_eq_weighted};
+pub use pcs::{CommittedOracle, PolynomialCommitment};
+pub use batched::{prove_batched, verify_batched};
+pub use streaming::{prove_mapped, prove_streaming, EvalSource, FnSource};
+pub use ext::{prove_ext, ExtOracle};
+pub use gkr::{prove_gkr, verify_gkr, Circuit, Gate, GateType};
+pub use spartan::{prove_r1cs, verify_r1cs, R1CS};
+pub use logup::{logup_multiplicities, prove_logup, verify_logup};
+pub use grand_product::{prove_grand_product, verify_grand_product};
+pub use permutation::{
+    permutation_mle, prove_multiset_eq, prove_permutation, verify_multiset_eq,
+    verify_permutation,
+};
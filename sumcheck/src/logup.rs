@@ -0,0 +1,253 @@
+//! LogUp lookup argument via GKR over fractions
+//!
+//! Every entry of a witness w (n_w variables) lies in a table t (n_t variables)
+//! iff, for multiplicities m counting how often each table entry is used,
+//!
+//! ∑_x 1 / (α − w(x)) = ∑_y m(y) / (α − t(y))
+//!
+//! as rational functions of α. The prover shows it for a random α by summing
+//! both sides as trees of fractions p/q (`tree`), with leaves (1, α − w) and
+//! (m, α − t). The verifier compares the two roots and is left with one
+//! evaluation claim on each of w, t and m.
+//!
+//! Fiat-Shamir: α is drawn from the transcript, so `prove_logup` /
+//! `verify_logup` take commitments to w, t and m and absorb them first.
+
+use std::collections::HashMap;
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::transcript::Transcript;
+use crate::tree::{prove_tree, verify_tree};
+use crate::types::{LogUpClaims, LogUpProof, LogUpProverOutput};
+use crate::virtual_poly::Composition;
+
+/// p = p_0 q_1 + p_1 q_0 and q = q_0 q_1, over the halves [p_0, p_1, q_0, q_1]
+fn fraction_rule<F: PrimeField>() -> Vec<Composition<F>> {
+    vec![
+        Composition {
+            num_mles: 4,
+            terms: vec![(F::ONE, vec![0, 3]), (F::ONE, vec![1, 2])],
+        },
+        Composition {
+            num_mles: 4,
+            terms: vec![(F::ONE, vec![2, 3])],
+        },
+    ]
+}
+
+/// Absorb the shapes and commitments and draw α
+fn sample_alpha<F: PrimeField, C: CanonicalSerialize>(
+    witness_vars: usize,
+    table_vars: usize,
+    commitments: &[C; 3],
+    transcript: &mut Transcript,
+) -> F {
+    transcript.append_message(b"protocol", b"logup");
+    transcript.append_u64(b"witness_vars", witness_vars as u64);
+    transcript.append_u64(b"table_vars", table_vars as u64);
+    transcript.append_commitments(commitments);
+    transcript.challenge_scalar(b"alpha")
+}
+
+/// α − f(x) for every x
+fn shifted<F: PrimeField>(alpha: F, poly: &MLPoly<F>) -> MLPoly<F> {
+    MLPoly {
+        n_vars: poly.n_vars,
+        evals: poly.evals.iter().map(|&v| alpha - v).collect(),
+    }
+}
+
+/// How often each table entry occurs in the witness
+///
+/// A value that appears several times in the table is counted at its first
+/// occurrence.
+///
+/// # Panics
+/// Panics if a witness entry is not in the table
+pub fn logup_multiplicities<F: PrimeField>(witness: &MLPoly<F>, table: &MLPoly<F>) -> MLPoly<F> {
+    let mut position = HashMap::with_capacity(table.len());
+    for (i, v) in table.evals.iter().enumerate() {
+        position.entry(*v).or_insert(i);
+    }
+    let mut counts = vec![0u64; table.len()];
+    for v in &witness.evals {
+        let i = position.get(v).expect("witness entry not in table");
+        counts[*i] += 1;
+    }
+    MLPoly {
+        n_vars: table.n_vars,
+        evals: counts.into_iter().map(F::from).collect(),
+    }
+}
+
+/// Prove that every entry of `witness` appears in `table`
+///
+/// # Arguments
+/// * `witness` - Looked-up values
+/// * `table` - Table of allowed values
+/// * `multiplicities` - Use count of every table entry, e.g. from `logup_multiplicities`
+/// * `commitments` - Commitments to witness, table and multiplicities, in that
+///   order, bound before α is drawn
+/// * `transcript` - Fiat-Shamir transcript
+///
+/// # Returns
+/// The proof and the evaluation claims on witness, table and multiplicities
+///
+/// # Panics
+/// Panics if `multiplicities` and `table` have different numbers of variables
+pub fn prove_logup<F: PrimeField, C: CanonicalSerialize>(
+    witness: &MLPoly<F>,
+    table: &MLPoly<F>,
+    multiplicities: &MLPoly<F>,
+    commitments: &[C; 3],
+    transcript: &mut Transcript,
+) -> LogUpProverOutput<F> {
+    assert_eq!(
+        multiplicities.n_vars, table.n_vars,
+        "multiplicities and table have different numbers of variables"
+    );
+    let alpha: F = sample_alpha(witness.n_vars, table.n_vars, commitments, transcript);
+    let rule = fraction_rule();
+
+    let ones = MLPoly::from_evals(vec![F::ONE; witness.len()]);
+    let (witness_tree, witness_point, witness_claims) =
+        prove_tree(&rule, vec![ones, shifted(alpha, witness)], transcript);
+    let (table_tree, table_point, table_claims) = prove_tree(
+        &rule,
+        vec![multiplicities.clone(), shifted(alpha, table)],
+        transcript,
+    );
+
+    LogUpProverOutput {
+        proof: LogUpProof {
+            witness: witness_tree,
+            table: table_tree,
+        },
+        claims: LogUpClaims {
+            witness_point,
+            witness_eval: alpha - witness_claims[1],
+            table_point,
+            table_eval: alpha - table_claims[1],
+            multiplicity_eval: table_claims[0],
+        },
+    }
+}
+
+/// Verify a LogUp proof
+///
+/// # Arguments
+/// * `witness_vars` - Number of variables of the witness
+/// * `table_vars` - Number of variables of the table and the multiplicities
+/// * `commitments` - Commitments to witness, table and multiplicities, as given
+///   to the prover
+/// * `proof` - The LogUp proof to verify
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(claims)` if the fractional sums agree; the caller must check each
+///   claimed evaluation against the commitment it was bound to
+/// * `Err(_)` if the sums differ or a tree layer fails
+pub fn verify_logup<F: PrimeField, C: CanonicalSerialize>(
+    witness_vars: usize,
+    table_vars: usize,
+    commitments: &[C; 3],
+    proof: &LogUpProof<F>,
+    transcript: &mut Transcript,
+) -> Result<LogUpClaims<F>> {
+    let alpha: F = sample_alpha(witness_vars, table_vars, commitments, transcript);
+    let rule = fraction_rule();
+
+    let (witness_point, witness_claims) =
+        verify_tree(&rule, witness_vars, &proof.witness, transcript)?;
+    let (table_point, table_claims) = verify_tree(&rule, table_vars, &proof.table, transcript)?;
+
+    // p_w / q_w == p_t / q_t, with both denominators nonzero
+    let (p_w, q_w) = (proof.witness.roots[0], proof.witness.roots[1]);
+    let (p_t, q_t) = (proof.table.roots[0], proof.table.roots[1]);
+    if q_w.is_zero() || q_t.is_zero() || p_w * q_t != p_t * q_w {
+        return Err(Error::InvalidProof("logup sums differ"));
+    }
+    // The witness numerators are the constant 1
+    if witness_claims[0] != F::ONE {
+        return Err(Error::InvalidProof("logup witness numerator is not 1"));
+    }
+
+    Ok(LogUpClaims {
+        witness_point,
+        witness_eval: alpha - witness_claims[1],
+        table_point,
+        table_eval: alpha - table_claims[1],
+        multiplicity_eval: table_claims[0],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::rand::Rng;
+    use ark_std::UniformRand;
+    use mlpoly::merkle::Digest;
+
+    fn commit(witness: &MLPoly<Fr>, table: &MLPoly<Fr>, m: &MLPoly<Fr>) -> [Digest; 3] {
+        [witness, table, m].map(|p| p.commit_merkle().root())
+    }
+
+    fn lookup(n_witness: usize, n_table: usize) -> (MLPoly<Fr>, MLPoly<Fr>) {
+        let mut rng = ark_std::test_rng();
+        let table: Vec<Fr> = (0..1 << n_table).map(|_| Fr::rand(&mut rng)).collect();
+        let witness = (0..1 << n_witness)
+            .map(|_| table[rng.gen_range(0..table.len())])
+            .collect();
+        (MLPoly::from_evals(witness), MLPoly::from_evals(table))
+    }
+
+    #[test]
+    fn test_logup_honest_prover() {
+        for (n_witness, n_table) in [(5, 3), (2, 4), (0, 0)] {
+            let (witness, table) = lookup(n_witness, n_table);
+            let m = logup_multiplicities(&witness, &table);
+            assert_eq!(m.sum_all(), Fr::from(1u64 << n_witness));
+
+            let commitments = commit(&witness, &table, &m);
+
+            let mut transcript = Transcript::new(b"logup-test");
+            let out = prove_logup(&witness, &table, &m, &commitments, &mut transcript);
+
+            let mut transcript = Transcript::new(b"logup-test");
+            let claims =
+                verify_logup(n_witness, n_table, &commitments, &out.proof, &mut transcript)
+                    .unwrap();
+            assert_eq!(claims, out.claims);
+            assert_eq!(claims.witness_eval, witness.eval_at(&claims.witness_point));
+            assert_eq!(claims.table_eval, table.eval_at(&claims.table_point));
+            assert_eq!(claims.multiplicity_eval, m.eval_at(&claims.table_point));
+        }
+    }
+
+    #[test]
+    fn test_logup_missing_entry_fails() {
+        let (mut witness, table) = lookup(4, 3);
+        let m = logup_multiplicities(&witness, &table);
+        // Same multiplicities, but one witness entry leaves the table
+        witness.evals[7] += Fr::from(1u64);
+        let commitments = commit(&witness, &table, &m);
+
+        let mut transcript = Transcript::new(b"logup-test");
+        let out = prove_logup(&witness, &table, &m, &commitments, &mut transcript);
+        let mut transcript = Transcript::new(b"logup-test");
+        assert!(verify_logup(4, 3, &commitments, &out.proof, &mut transcript).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "witness entry not in table")]
+    fn test_multiplicities_reject_missing_entry() {
+        let table = MLPoly::from_evals(vec![Fr::from(1u64), Fr::from(2u64)]);
+        let witness = MLPoly::from_evals(vec![Fr::from(1u64), Fr::from(3u64)]);
+        logup_multiplicities(&witness, &table);
+    }
+}
@@ -1,29 +1,60 @@
 //! Oracle trait for polynomial evaluation queries
 
-use ark_ff::PrimeField;
-use mlpoly::MLPoly;
+use std::marker::PhantomData;
+
+use ark_ff::Field;
+use mlpoly::{eq_eval, MLPoly, MultilinearPolynomial};
+
+use crate::error::Result;
 
 /// Oracle that answers point queries on the polynomial
-pub trait Oracle<F: PrimeField> {
+pub trait Oracle<F: Field> {
     /// Evaluate the polynomial at point x
-    fn query(&self, x: &[F]) -> F;
+    ///
+    /// Returns an error if the answer cannot be authenticated, e.g. when an
+    /// opening proof backing the evaluation fails to verify.
+    fn query(&self, x: &[F]) -> Result<F>;
 }
 
 /// Concrete oracle wrapping a multilinear polynomial
 /// 
-/// Used for testing or when the verifier has direct access to the polynomial
-pub struct PolyOracle<F: PrimeField> {
-    pub poly: MLPoly<F>,
+/// Used for testing or when the verifier has direct access to the polynomial,
+/// in any `MultilinearPolynomial` representation (dense by default)
+pub struct PolyOracle<F: Field, P: MultilinearPolynomial<F> = MLPoly<F>> {
+    pub poly: P,
+    _field: PhantomData<F>,
+}
+
+impl<F: Field, P: MultilinearPolynomial<F>> PolyOracle<F, P> {
+    pub fn new(poly: P) -> Self {
+        Self {
+            poly,
+            _field: PhantomData,
+        }
+    }
+}
+
+impl<F: Field, P: MultilinearPolynomial<F>> Oracle<F> for PolyOracle<F, P> {
+    fn query(&self, x: &[F]) -> Result<F> {
+        Ok(self.poly.eval_at(x))
+    }
+}
+
+/// Oracle for the public polynomial eq(τ, x) = ∏_i (τ_i x_i + (1 - τ_i)(1 - x_i))
+///
+/// The verifier evaluates it in O(n) without any help from the prover.
+pub struct EqOracle<F: Field> {
+    pub tau: Vec<F>,
 }
 
-impl<F: PrimeField> PolyOracle<F> {
-    pub fn new(poly: MLPoly<F>) -> Self {
-        Self { poly }
+impl<F: Field> EqOracle<F> {
+    pub fn new(tau: Vec<F>) -> Self {
+        Self { tau }
     }
 }
 
-impl<F: PrimeField> Oracle<F> for PolyOracle<F> {
-    fn query(&self, x: &[F]) -> F {
-        self.poly.eval_at(x)
+impl<F: Field> Oracle<F> for EqOracle<F> {
+    fn query(&self, x: &[F]) -> Result<F> {
+        Ok(eq_eval(&self.tau, x))
     }
 }
@@ -0,0 +1,88 @@
+//! Polynomial commitments backing the verifier's final oracle query
+//!
+//! With a `PolynomialCommitment`, the verifier never holds the polynomial: it
+//! receives a commitment up front and, after the sumcheck rounds, an evaluation
+//! at the challenge point together with an opening proof. `CommittedOracle`
+//! packages these so that they plug into any `verify*` function as an `Oracle`.
+
+pub mod kzg;
+
+use ark_ff::PrimeField;
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+
+/// A commitment scheme for multilinear polynomials in evaluation form
+pub trait PolynomialCommitment<F: PrimeField> {
+    /// Key used to commit and open
+    type ProverKey;
+    /// Key used to check openings
+    type VerifierKey;
+    /// Binding commitment to a polynomial
+    type Commitment: Clone;
+    /// Proof that a committed polynomial takes a given value at a point
+    type Proof: Clone;
+
+    /// Commit to `poly`
+    fn commit(pk: &Self::ProverKey, poly: &MLPoly<F>) -> Self::Commitment;
+
+    /// Evaluate `poly` at `point` and prove the evaluation
+    ///
+    /// Returns (poly(point), proof).
+    fn open(pk: &Self::ProverKey, poly: &MLPoly<F>, point: &[F]) -> (F, Self::Proof);
+
+    /// Check that the polynomial behind `commitment` evaluates to `value` at `point`
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        proof: &Self::Proof,
+    ) -> bool;
+}
+
+/// Oracle answered by a commitment plus an opening proof at a single point
+///
+/// The prover supplies `value` and `proof` for the point it derived from the
+/// transcript; a query at any other point, or with a proof that does not verify,
+/// returns `Error::InvalidOpening`.
+pub struct CommittedOracle<'a, F: PrimeField, P: PolynomialCommitment<F>> {
+    pub vk: &'a P::VerifierKey,
+    pub commitment: P::Commitment,
+    pub point: Vec<F>,
+    pub value: F,
+    pub proof: P::Proof,
+}
+
+impl<'a, F: PrimeField, P: PolynomialCommitment<F>> CommittedOracle<'a, F, P> {
+    pub fn new(
+        vk: &'a P::VerifierKey,
+        commitment: P::Commitment,
+        point: Vec<F>,
+        value: F,
+        proof: P::Proof,
+    ) -> Self {
+        Self {
+            vk,
+            commitment,
+            point,
+            value,
+            proof,
+        }
+    }
+}
+
+impl<F: PrimeField, P: PolynomialCommitment<F>> Oracle<F> for CommittedOracle<'_, F, P> {
+    fn query(&self, x: &[F]) -> Result<F> {
+        if x != self.point.as_slice() {
+            return Err(Error::InvalidOpening(
+                "query point differs from opened point",
+            ));
+        }
+        if !P::verify(self.vk, &self.commitment, x, self.value, &self.proof) {
+            return Err(Error::InvalidOpening("opening proof failed"));
+        }
+        Ok(self.value)
+    }
+}
@@ -0,0 +1,257 @@
+//! Multilinear KZG commitments (Papamanthou–Shi–Tamassia)
+//!
+//! For a secret τ ∈ F^n the commitment to f is g^{f(τ)}. Any multilinear f
+//! satisfies f(x) - f(z) = ∑_i (x_i - z_i) · q_i(x_{i+1}, ..., x_n), where q_i is
+//! the difference f(z_1, ..., z_{i-1}, 1, ·) - f(z_1, ..., z_{i-1}, 0, ·). The
+//! opening proof is the commitments g^{q_i(τ)}, checked with one pairing equation
+//! e(C · g^{-v}, h) = ∏_i e(g^{q_i(τ)}, h^{τ_i - z_i}).
+
+use std::marker::PhantomData;
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, Group, VariableBaseMSM};
+use ark_ff::UniformRand;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use mlpoly::MLPoly;
+use rand_core::RngCore;
+
+use super::PolynomialCommitment;
+
+/// Multilinear KZG over the pairing `E`
+pub struct MultilinearKzg<E: Pairing>(PhantomData<E>);
+
+/// Structured reference string for committing and opening
+#[derive(Clone, Debug)]
+pub struct KzgProverKey<E: Pairing> {
+    /// `bases[k][y] = g^{eq((τ_{k+1}, ..., τ_n), y)}`: the Lagrange basis over the
+    /// last n - k variables; `bases[0]` commits n-variate polynomials
+    pub bases: Vec<Vec<E::G1Affine>>,
+}
+
+/// Verifier key: the generators and h^{τ_i}
+#[derive(Clone, Debug)]
+pub struct KzgVerifierKey<E: Pairing> {
+    pub n_vars: usize,
+    pub g: E::G1Affine,
+    pub h: E::G2Affine,
+    pub h_tau: Vec<E::G2Affine>,
+}
+
+/// Commitment g^{f(τ)}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgCommitment<E: Pairing>(pub E::G1Affine);
+
+/// Opening proof: one commitment per quotient q_1, ..., q_n
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgProof<E: Pairing> {
+    pub quotients: Vec<E::G1Affine>,
+}
+
+impl<E: Pairing> MultilinearKzg<E> {
+    /// Trusted setup for polynomials in `n_vars` variables
+    ///
+    /// τ is sampled from `rng` and dropped; whoever controls `rng` can forge openings.
+    pub fn setup<R: RngCore>(n_vars: usize, rng: &mut R) -> (KzgProverKey<E>, KzgVerifierKey<E>) {
+        let tau: Vec<E::ScalarField> = (0..n_vars).map(|_| E::ScalarField::rand(rng)).collect();
+        let g = E::G1::generator();
+        let h = E::G2::generator();
+
+        let bases = (0..=n_vars)
+            .map(|k| {
+                let scalars = MLPoly::eq_table(&tau[k..]).evals;
+                let points: Vec<E::G1> = scalars.iter().map(|s| g * s).collect();
+                E::G1::normalize_batch(&points)
+            })
+            .collect();
+        let h_tau: Vec<E::G2> = tau.iter().map(|t| h * t).collect();
+
+        let pk = KzgProverKey { bases };
+        let vk = KzgVerifierKey {
+            n_vars,
+            g: g.into_affine(),
+            h: h.into_affine(),
+            h_tau: E::G2::normalize_batch(&h_tau),
+        };
+        (pk, vk)
+    }
+}
+
+impl<E: Pairing> PolynomialCommitment<E::ScalarField> for MultilinearKzg<E> {
+    type ProverKey = KzgProverKey<E>;
+    type VerifierKey = KzgVerifierKey<E>;
+    type Commitment = KzgCommitment<E>;
+    type Proof = KzgProof<E>;
+
+    fn commit(pk: &Self::ProverKey, poly: &MLPoly<E::ScalarField>) -> Self::Commitment {
+        assert_eq!(
+            pk.bases[0].len(),
+            poly.len(),
+            "polynomial does not match the setup size"
+        );
+        KzgCommitment(E::G1::msm_unchecked(&pk.bases[0], &poly.evals).into_affine())
+    }
+
+    fn open(
+        pk: &Self::ProverKey,
+        poly: &MLPoly<E::ScalarField>,
+        point: &[E::ScalarField],
+    ) -> (E::ScalarField, Self::Proof) {
+        assert_eq!(
+            pk.bases[0].len(),
+            poly.len(),
+            "polynomial does not match the setup size"
+        );
+        assert_eq!(
+            point.len(),
+            poly.n_vars,
+            "wrong number of evaluation points"
+        );
+
+        let mut current = poly.clone();
+        let mut quotients = Vec::with_capacity(point.len());
+        for (i, &z) in point.iter().enumerate() {
+            // q_i(y) = f(.., 1, y) - f(.., 0, y), committed over the remaining variables
+            let q: Vec<E::ScalarField> = current
+                .evals
+                .chunks(2)
+                .map(|pair| pair[1] - pair[0])
+                .collect();
+            quotients.push(E::G1::msm_unchecked(&pk.bases[i + 1], &q));
+            current = current.fold_first_var(z);
+        }
+
+        let proof = KzgProof {
+            quotients: E::G1::normalize_batch(&quotients),
+        };
+        (current.evals[0], proof)
+    }
+
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: &[E::ScalarField],
+        value: E::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        if point.len() != vk.n_vars || proof.quotients.len() != vk.n_vars {
+            return false;
+        }
+
+        // e(C - g^v, h) == ∏_i e(π_i, h^{τ_i} - h^{z_i})
+        let lhs = E::pairing(commitment.0.into_group() - vk.g * value, vk.h);
+        let rhs_g2: Vec<E::G2> = vk
+            .h_tau
+            .iter()
+            .zip(point)
+            .map(|(h_tau, z)| h_tau.into_group() - vk.h * z)
+            .collect();
+        let rhs = E::multi_pairing(&proof.quotients, E::G2::normalize_batch(&rhs_g2));
+        lhs == rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::Oracle;
+    use crate::pcs::CommittedOracle;
+    use crate::protocol::{prove_with_output, verify_with_config};
+    use crate::transcript::Transcript;
+    use crate::types::{Statement, SumcheckConfig};
+    use crate::Error;
+    use ark_bn254::{Bn254, Fr};
+
+    type Kzg = MultilinearKzg<Bn254>;
+
+    fn random_poly(n_vars: usize, rng: &mut impl ark_std::rand::Rng) -> MLPoly<Fr> {
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(rng)).collect();
+        MLPoly { n_vars, evals }
+    }
+
+    #[test]
+    fn test_kzg_open_verify() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 4;
+        let (pk, vk) = Kzg::setup(n_vars, &mut rng);
+
+        let poly = random_poly(n_vars, &mut rng);
+        let comm = Kzg::commit(&pk, &poly);
+        let point: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+
+        let (value, proof) = Kzg::open(&pk, &poly, &point);
+        assert_eq!(value, poly.eval_at(&point));
+        assert!(Kzg::verify(&vk, &comm, &point, value, &proof));
+
+        // Wrong value, wrong point, wrong commitment
+        assert!(!Kzg::verify(
+            &vk,
+            &comm,
+            &point,
+            value + Fr::from(1u64),
+            &proof
+        ));
+        let mut other_point = point.clone();
+        other_point[0] += Fr::from(1u64);
+        assert!(!Kzg::verify(&vk, &comm, &other_point, value, &proof));
+        let other_comm = Kzg::commit(&pk, &random_poly(n_vars, &mut rng));
+        assert!(!Kzg::verify(&vk, &other_comm, &point, value, &proof));
+    }
+
+    #[test]
+    fn test_sumcheck_with_committed_oracle() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 4;
+        let (pk, vk) = Kzg::setup(n_vars, &mut rng);
+
+        let poly = random_poly(n_vars, &mut rng);
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+
+        // Prover: commit, bind the commitment, run sumcheck, open at the challenge point
+        let comm = Kzg::commit(&pk, &poly);
+        let mut comm_bytes = Vec::new();
+        comm.serialize_compressed(&mut comm_bytes).unwrap();
+        let config = SumcheckConfig::with_public_input(comm_bytes);
+
+        let mut transcript = Transcript::new(b"kzg-test");
+        let out = prove_with_output(&stmt, &poly, &mut transcript, &config);
+        let (value, opening) = Kzg::open(&pk, &poly, &out.point);
+
+        // Verifier: only sees the commitment, the proof and the opening
+        let oracle =
+            CommittedOracle::<Fr, Kzg>::new(&vk, comm, out.point.clone(), value, opening.clone());
+        let mut transcript = Transcript::new(b"kzg-test");
+        let result = verify_with_config(&stmt, &out.proof, &oracle, &mut transcript, &config);
+        assert!(result.unwrap(), "committed sumcheck should verify");
+
+        // A lying evaluation is caught by the opening check
+        let bad = CommittedOracle::<Fr, Kzg>::new(
+            &vk,
+            comm,
+            out.point.clone(),
+            value + Fr::from(1u64),
+            opening,
+        );
+        let mut transcript = Transcript::new(b"kzg-test");
+        let result = verify_with_config(&stmt, &out.proof, &bad, &mut transcript, &config);
+        assert_eq!(result, Err(Error::InvalidOpening("opening proof failed")));
+    }
+
+    #[test]
+    fn test_committed_oracle_rejects_other_point() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 2;
+        let (pk, vk) = Kzg::setup(n_vars, &mut rng);
+
+        let poly = random_poly(n_vars, &mut rng);
+        let point: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+        let (value, proof) = Kzg::open(&pk, &poly, &point);
+        let oracle =
+            CommittedOracle::<Fr, Kzg>::new(&vk, Kzg::commit(&pk, &poly), point, value, proof);
+
+        let other: Vec<Fr> = (0..n_vars).map(|_| Fr::rand(&mut rng)).collect();
+        assert!(oracle.query(&other).is_err());
+    }
+}
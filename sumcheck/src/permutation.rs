@@ -0,0 +1,329 @@
+//! Permutation and multiset-equality checks on top of the grand product
+//!
+//! Multiset equality: {f(x)} = {g(x)} as multisets iff
+//! ∏_x (α − f(x)) = ∏_x (α − g(x)) for a random α.
+//!
+//! Permutation (HyperPlonk-style): for a public permutation σ of {0,1}^n given
+//! as its index MLE (σ(x) stored as a field element, likewise id(x) = x read as
+//! an integer), g(x) = f(σ(x)) for all x iff the pairs {(id(x), f(x))} and
+//! {(σ(x), g(x))} agree as multisets, which is checked as
+//! ∏_x (f(x) + β·id(x) + γ) = ∏_x (g(x) + β·σ(x) + γ) for random β, γ.
+//!
+//! Both reduce to one grand product proof over two polynomials and leave the
+//! verifier with evaluation claims at a shared point. id is evaluated by the
+//! verifier directly; the claims on f, g and σ go to their oracles. Both checks
+//! take commitments to their polynomials and absorb them, with n, before
+//! drawing α or β, γ.
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use mlpoly::MLPoly;
+
+use crate::error::{Error, Result};
+use crate::grand_product::{prove_products, verify_products};
+use crate::transcript::Transcript;
+use crate::types::{
+    GrandProductProof, MultisetClaims, MultisetProverOutput, PermutationClaims, PermutationProof,
+    PermutationProverOutput,
+};
+
+/// The index MLE id(x) = ∑_k 2^{k-1} x_k
+fn identity<F: PrimeField>(n_vars: usize) -> MLPoly<F> {
+    MLPoly::from_evals((0..1u64 << n_vars).map(F::from).collect())
+}
+
+/// id(r) in O(n)
+fn identity_eval<F: PrimeField>(point: &[F]) -> F {
+    point.iter().rev().fold(F::ZERO, |acc, &r| acc.double() + r)
+}
+
+/// f(x) + β·s(x) + γ for every x
+fn shifted_pair<F: PrimeField>(f: &MLPoly<F>, s: &MLPoly<F>, beta: F, gamma: F) -> MLPoly<F> {
+    MLPoly {
+        n_vars: f.n_vars,
+        evals: f
+            .evals
+            .iter()
+            .zip(&s.evals)
+            .map(|(&v, &i)| v + beta * i + gamma)
+            .collect(),
+    }
+}
+
+/// α − f(x) for every x
+fn shifted<F: PrimeField>(alpha: F, poly: &MLPoly<F>) -> MLPoly<F> {
+    MLPoly {
+        n_vars: poly.n_vars,
+        evals: poly.evals.iter().map(|&v| alpha - v).collect(),
+    }
+}
+
+/// Both products of a proof, which must be equal
+fn equal_products<F: PrimeField>(proof: &GrandProductProof<F>) -> Result<[F; 2]> {
+    match *proof.products() {
+        [a, b] if a == b => Ok([a, b]),
+        [_, _] => Err(Error::InvalidProof("products differ")),
+        _ => Err(Error::DimensionMismatch("expected two products")),
+    }
+}
+
+/// Absorb the protocol, shape and commitments ahead of the challenges
+fn absorb<C: CanonicalSerialize>(
+    protocol: &'static [u8],
+    n_vars: usize,
+    commitments: &[C],
+    transcript: &mut Transcript,
+) {
+    transcript.append_message(b"protocol", protocol);
+    transcript.append_u64(b"n_vars", n_vars as u64);
+    transcript.append_commitments(commitments);
+}
+
+/// Index MLE of a permutation of {0, ..., 2^n - 1}: σ(x) = perm[x]
+///
+/// # Panics
+/// Panics if `perm.len()` is not a power of two
+pub fn permutation_mle<F: PrimeField>(perm: &[usize]) -> MLPoly<F> {
+    MLPoly::from_evals(perm.iter().map(|&i| F::from(i as u64)).collect())
+}
+
+/// Prove g(x) = f(σ(x)) for every x ∈ {0,1}^n
+///
+/// # Arguments
+/// * `f`, `g` - The two polynomials
+/// * `sigma` - Index MLE of the public permutation, e.g. from `permutation_mle`
+/// * `commitments` - Commitments to f, g and σ, in that order, bound before β, γ
+/// * `transcript` - Fiat-Shamir transcript
+///
+/// # Returns
+/// The proof and the evaluation claims on f, g and σ
+///
+/// # Panics
+/// Panics if the three polynomials differ in size
+pub fn prove_permutation<F: PrimeField, C: CanonicalSerialize>(
+    f: &MLPoly<F>,
+    g: &MLPoly<F>,
+    sigma: &MLPoly<F>,
+    commitments: &[C; 3],
+    transcript: &mut Transcript,
+) -> PermutationProverOutput<F> {
+    assert!(
+        f.n_vars == g.n_vars && f.n_vars == sigma.n_vars,
+        "f, g and sigma have different numbers of variables"
+    );
+    absorb(b"permutation", f.n_vars, commitments, transcript);
+    let beta: F = transcript.challenge_scalar(b"beta");
+    let gamma: F = transcript.challenge_scalar(b"gamma");
+
+    let leaves = vec![
+        shifted_pair(f, &identity(f.n_vars), beta, gamma),
+        shifted_pair(g, sigma, beta, gamma),
+    ];
+    let out = prove_products(leaves, transcript);
+    let point = out.claims.point;
+    let sigma_eval = sigma.eval_at(&point);
+    transcript.append_field(b"sigma_eval", &sigma_eval);
+
+    let f_eval = out.claims.evals[0] - beta * identity_eval(&point) - gamma;
+    let g_eval = out.claims.evals[1] - beta * sigma_eval - gamma;
+    PermutationProverOutput {
+        proof: PermutationProof {
+            products: out.proof,
+            sigma_eval,
+        },
+        claims: PermutationClaims {
+            point,
+            f_eval,
+            g_eval,
+            sigma_eval,
+        },
+    }
+}
+
+/// Verify a permutation proof
+///
+/// # Arguments
+/// * `n_vars` - Number of variables of f, g and σ
+/// * `commitments` - Commitments to f, g and σ, as given to the prover
+/// * `proof` - The permutation proof to verify
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(claims)` if the products agree and the product proof checks out; the
+///   caller must check `f_eval`, `g_eval` and `sigma_eval` against the
+///   commitments
+/// * `Err(_)` otherwise
+pub fn verify_permutation<F: PrimeField, C: CanonicalSerialize>(
+    n_vars: usize,
+    commitments: &[C; 3],
+    proof: &PermutationProof<F>,
+    transcript: &mut Transcript,
+) -> Result<PermutationClaims<F>> {
+    absorb(b"permutation", n_vars, commitments, transcript);
+    let beta: F = transcript.challenge_scalar(b"beta");
+    let gamma: F = transcript.challenge_scalar(b"gamma");
+
+    let products = equal_products(&proof.products)?;
+    let claims = verify_products(n_vars, &products, &proof.products, transcript)?;
+    transcript.append_field(b"sigma_eval", &proof.sigma_eval);
+
+    let f_eval = claims.evals[0] - beta * identity_eval(&claims.point) - gamma;
+    let g_eval = claims.evals[1] - beta * proof.sigma_eval - gamma;
+    Ok(PermutationClaims {
+        point: claims.point,
+        f_eval,
+        g_eval,
+        sigma_eval: proof.sigma_eval,
+    })
+}
+
+/// Prove that f and g take the same values with the same multiplicities
+///
+/// # Arguments
+/// * `f`, `g` - The two polynomials
+/// * `commitments` - Commitments to f and g, in that order, bound before α
+/// * `transcript` - Fiat-Shamir transcript
+///
+/// # Returns
+/// The proof and the evaluation claims on f and g
+///
+/// # Panics
+/// Panics if f and g differ in size
+pub fn prove_multiset_eq<F: PrimeField, C: CanonicalSerialize>(
+    f: &MLPoly<F>,
+    g: &MLPoly<F>,
+    commitments: &[C; 2],
+    transcript: &mut Transcript,
+) -> MultisetProverOutput<F> {
+    assert_eq!(
+        f.n_vars, g.n_vars,
+        "f and g have different numbers of variables"
+    );
+    absorb(b"multiset", f.n_vars, commitments, transcript);
+    let alpha: F = transcript.challenge_scalar(b"alpha");
+
+    let out = prove_products(vec![shifted(alpha, f), shifted(alpha, g)], transcript);
+    MultisetProverOutput {
+        proof: out.proof,
+        claims: MultisetClaims {
+            f_eval: alpha - out.claims.evals[0],
+            g_eval: alpha - out.claims.evals[1],
+            point: out.claims.point,
+        },
+    }
+}
+
+/// Verify a multiset-equality proof
+///
+/// # Arguments
+/// * `n_vars` - Number of variables of f and g
+/// * `commitments` - Commitments to f and g, as given to the prover
+/// * `proof` - The product proof to verify
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(claims)` if the products agree and the product proof checks out; the
+///   caller must check `f_eval` and `g_eval` against the commitments
+/// * `Err(_)` otherwise
+pub fn verify_multiset_eq<F: PrimeField, C: CanonicalSerialize>(
+    n_vars: usize,
+    commitments: &[C; 2],
+    proof: &GrandProductProof<F>,
+    transcript: &mut Transcript,
+) -> Result<MultisetClaims<F>> {
+    absorb(b"multiset", n_vars, commitments, transcript);
+    let alpha: F = transcript.challenge_scalar(b"alpha");
+
+    let products = equal_products(proof)?;
+    let claims = verify_products(n_vars, &products, proof, transcript)?;
+    Ok(MultisetClaims {
+        f_eval: alpha - claims.evals[0],
+        g_eval: alpha - claims.evals[1],
+        point: claims.point,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::rand::seq::SliceRandom;
+    use ark_std::UniformRand;
+    use mlpoly::merkle::Digest;
+
+    fn commit<const N: usize>(polys: [&MLPoly<Fr>; N]) -> [Digest; N] {
+        polys.map(|p| p.commit_merkle().root())
+    }
+
+    /// f, a random permutation σ and g = f ∘ σ
+    fn permuted(n_vars: usize) -> (MLPoly<Fr>, MLPoly<Fr>, Vec<usize>) {
+        let mut rng = ark_std::test_rng();
+        let f: Vec<Fr> = (0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect();
+        let mut perm: Vec<usize> = (0..1 << n_vars).collect();
+        perm.shuffle(&mut rng);
+        let g = perm.iter().map(|&i| f[i]).collect();
+        (MLPoly::from_evals(f), MLPoly::from_evals(g), perm)
+    }
+
+    #[test]
+    fn test_identity_eval() {
+        let mut rng = ark_std::test_rng();
+        let point: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+        assert_eq!(identity_eval(&point), identity::<Fr>(5).eval_at(&point));
+    }
+
+    #[test]
+    fn test_permutation_honest_prover() {
+        for n_vars in [0, 1, 5] {
+            let (f, g, perm) = permuted(n_vars);
+            let sigma = permutation_mle(&perm);
+            let commitments = commit([&f, &g, &sigma]);
+
+            let mut transcript = Transcript::new(b"permutation-test");
+            let out = prove_permutation(&f, &g, &sigma, &commitments, &mut transcript);
+
+            let mut transcript = Transcript::new(b"permutation-test");
+            let claims =
+                verify_permutation(n_vars, &commitments, &out.proof, &mut transcript).unwrap();
+            assert_eq!(claims, out.claims);
+            assert_eq!(claims.f_eval, f.eval_at(&claims.point));
+            assert_eq!(claims.g_eval, g.eval_at(&claims.point));
+            assert_eq!(claims.sigma_eval, sigma.eval_at(&claims.point));
+        }
+    }
+
+    #[test]
+    fn test_wrong_permutation_fails_but_multiset_holds() {
+        let (f, mut g, perm) = permuted(4);
+        g.evals.swap(0, 1);
+        let sigma = permutation_mle(&perm);
+        let commitments = commit([&f, &g, &sigma]);
+
+        let mut transcript = Transcript::new(b"permutation-test");
+        let out = prove_permutation(&f, &g, &sigma, &commitments, &mut transcript);
+        let mut transcript = Transcript::new(b"permutation-test");
+        assert!(verify_permutation(4, &commitments, &out.proof, &mut transcript).is_err());
+
+        // Same values, so the multisets still agree
+        let commitments = commit([&f, &g]);
+        let mut transcript = Transcript::new(b"multiset-test");
+        let out = prove_multiset_eq(&f, &g, &commitments, &mut transcript);
+        let mut transcript = Transcript::new(b"multiset-test");
+        let claims = verify_multiset_eq(4, &commitments, &out.proof, &mut transcript).unwrap();
+        assert_eq!(claims, out.claims);
+        assert_eq!(claims.f_eval, f.eval_at(&claims.point));
+        assert_eq!(claims.g_eval, g.eval_at(&claims.point));
+    }
+
+    #[test]
+    fn test_multiset_different_values_fails() {
+        let (f, mut g, _) = permuted(4);
+        g.evals[3] = g.evals[5];
+        let commitments = commit([&f, &g]);
+
+        let mut transcript = Transcript::new(b"multiset-test");
+        let out = prove_multiset_eq(&f, &g, &commitments, &mut transcript);
+        let mut transcript = Transcript::new(b"multiset-test");
+        assert!(verify_multiset_eq(4, &commitments, &out.proof, &mut transcript).is_err());
+    }
+}
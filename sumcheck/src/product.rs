@@ -0,0 +1,197 @@
+//! Sumcheck for a product of multilinear polynomials
+//!
+//! Proves ∑_{x ∈ {0,1}^n} f_1(x) · f_2(x) · ... · f_k(x) = claim.
+//! Each round polynomial has degree k, so the prover sends k+1 evaluations
+//! g(0), g(1), ..., g(k) and the verifier interpolates to evaluate at the challenge.
+
+use ark_ff::PrimeField;
+use mlpoly::MLPoly;
+
+use crate::error::Result;
+use crate::oracle::Oracle;
+use crate::transcript::Transcript;
+use crate::types::{ProverOutput, Statement, SumcheckProof};
+use crate::virtual_poly::{prove_virtual, verify_virtual, Composition, VirtualPoly};
+
+/// The composition G(f_1, ..., f_k) = f_1 · ... · f_k
+pub(crate) fn product_composition<F: PrimeField>(k: usize) -> Composition<F> {
+    Composition {
+        num_mles: k,
+        terms: vec![(F::ONE, (0..k).collect())],
+    }
+}
+
+/// Generate a sumcheck proof for ∑_x ∏_i f_i(x)
+///
+/// # Arguments
+/// * `stmt` - Public statement; `claim_sum` is the claimed sum of the product
+/// * `factors` - The multilinear factors f_1, ..., f_k, all with `stmt.n_vars` variables
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The proof (one degree-k round polynomial per variable), the challenge point
+/// and the evaluation of every factor at that point
+///
+/// # Panics
+/// Panics if `factors` is empty or a factor has the wrong number of variables
+pub fn prove_product<F: PrimeField>(
+    stmt: &Statement<F>,
+    factors: &[MLPoly<F>],
+    transcript: &mut Transcript,
+) -> ProverOutput<F> {
+    let refs: Vec<&MLPoly<F>> = factors.iter().collect();
+    let mut poly = VirtualPoly::new(stmt.n_vars);
+    poly.add_product(F::ONE, &refs);
+    prove_virtual(stmt, &poly, transcript)
+}
+
+/// Verify a sumcheck proof for ∑_x ∏_i f_i(x)
+///
+/// # Arguments
+/// * `stmt` - Public statement containing n_vars and claimed sum
+/// * `proof` - The sumcheck proof to verify
+/// * `oracles` - One oracle per factor, in the prover's order
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final oracle check fails
+/// * `Err(_)` if a round check fails
+pub fn verify_product<F: PrimeField>(
+    stmt: &Statement<F>,
+    proof: &SumcheckProof<F>,
+    oracles: &[&dyn Oracle<F>],
+    transcript: &mut Transcript,
+) -> Result<bool> {
+    let composition = product_composition(oracles.len());
+    verify_virtual(stmt, &composition, proof, oracles, transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize, rng: &mut impl ark_std::rand::Rng) -> MLPoly<Fr> {
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(rng)).collect();
+        MLPoly { n_vars, evals }
+    }
+
+    fn product_sum(factors: &[MLPoly<Fr>]) -> Fr {
+        (0..factors[0].len())
+            .map(|i| factors.iter().map(|f| f.evals[i]).product::<Fr>())
+            .sum()
+    }
+
+    #[test]
+    fn test_product_sumcheck_honest_prover() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 4;
+
+        for k in 1..=4 {
+            let factors: Vec<MLPoly<Fr>> = (0..k).map(|_| random_poly(n_vars, &mut rng)).collect();
+            let stmt = Statement {
+                n_vars,
+                claim_sum: product_sum(&factors),
+            };
+
+            let mut transcript = Transcript::new(b"product-test");
+            let out = prove_product(&stmt, &factors, &mut transcript);
+            assert!(out.proof.round_polys.iter().all(|g| g.degree() == k));
+
+            let oracles: Vec<PolyOracle<Fr>> = factors.into_iter().map(PolyOracle::new).collect();
+            let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+            let mut transcript = Transcript::new(b"product-test");
+            let result = verify_product(&stmt, &out.proof, &refs, &mut transcript);
+
+            assert!(
+                result.unwrap(),
+                "honest proof with {} factors should verify",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn test_product_final_evals_match_point() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 3;
+
+        let factors: Vec<MLPoly<Fr>> = (0..3).map(|_| random_poly(n_vars, &mut rng)).collect();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: product_sum(&factors),
+        };
+
+        let mut transcript = Transcript::new(b"product-test");
+        let out = prove_product(&stmt, &factors, &mut transcript);
+
+        for (f, e) in factors.iter().zip(&out.final_evals) {
+            assert_eq!(f.eval_at(&out.point), *e);
+        }
+    }
+
+    #[test]
+    fn test_product_wrong_claim_fails() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 3;
+
+        let factors: Vec<MLPoly<Fr>> = (0..2).map(|_| random_poly(n_vars, &mut rng)).collect();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: product_sum(&factors) + Fr::from(1u64),
+        };
+
+        let mut transcript = Transcript::new(b"product-test");
+        let out = prove_product(&stmt, &factors, &mut transcript);
+
+        let oracles: Vec<PolyOracle<Fr>> = factors.into_iter().map(PolyOracle::new).collect();
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"product-test");
+        let result = verify_product(&stmt, &out.proof, &refs, &mut transcript);
+
+        assert!(result.is_err(), "wrong claim should fail verification");
+    }
+
+    #[test]
+    fn test_product_wrong_degree_rejected() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 2;
+
+        let factors: Vec<MLPoly<Fr>> = (0..2).map(|_| random_poly(n_vars, &mut rng)).collect();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: product_sum(&factors),
+        };
+
+        let mut transcript = Transcript::new(b"product-test");
+        let out = prove_product(&stmt, &factors, &mut transcript);
+
+        // Verifier expects three factors, i.e. degree-3 round polynomials
+        let oracles: Vec<PolyOracle<Fr>> = (0..3)
+            .map(|_| PolyOracle::new(random_poly(n_vars, &mut rng)))
+            .collect();
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"product-test");
+        let result = verify_product(&stmt, &out.proof, &refs, &mut transcript);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_product_without_oracles_rejected() {
+        let stmt = Statement {
+            n_vars: 1,
+            claim_sum: Fr::from(3u64),
+        };
+        let proof = SumcheckProof {
+            round_polys: vec![crate::types::RoundPoly {
+                evals: vec![Fr::from(3u64)],
+            }],
+        };
+        let mut transcript = Transcript::new(b"product-test");
+        assert!(verify_product(&stmt, &proof, &[], &mut transcript).is_err());
+    }
+}
@@ -1,51 +1,301 @@
 //! Non-interactive sumcheck protocol using Fiat-Shamir transform
 
-use ark_ff::PrimeField;
-use mlpoly::MLPoly;
+use ark_ff::Field;
+use mlpoly::{MLPoly, MultilinearPolynomial, SmallMLPoly, SmallValue};
 
 use crate::error::{Error, Result};
 use crate::oracle::Oracle;
 use crate::transcript::Transcript;
-use crate::types::{RoundPoly, Statement, SumcheckProof};
+use crate::types::{
+    BindingOrder, ProverOutput, RoundPoly, Statement, SubClaim, SumcheckConfig, SumcheckProof,
+    STATEMENT_DOMAIN,
+};
+
+/// Absorb the public statement into the transcript before round 1
+///
+/// Both sides must call this with identical inputs so that every challenge
+/// depends on the claim being proven.
+pub(crate) fn absorb_statement<F: Field>(
+    stmt: &Statement<F>,
+    config: &SumcheckConfig,
+    transcript: &mut Transcript,
+) {
+    if !config.bind_statement {
+        return;
+    }
+    transcript.append_message(b"domain", STATEMENT_DOMAIN);
+    transcript.append_u64(b"n_vars", stmt.n_vars as u64);
+    transcript.append_field(b"claim_sum", &stmt.claim_sum);
+    transcript.append_message(b"public_input", &config.public_input);
+}
+
+/// Absorb a round polynomial into the transcript
+///
+/// g(0) and g(1) keep their own labels; evaluations at 2, ..., d follow.
+/// The degree itself is fixed by the statement, not by the proof.
+pub(crate) fn append_round_poly<F: Field>(round_poly: &RoundPoly<F>, transcript: &mut Transcript) {
+    transcript.append_field(b"g0", &round_poly.eval_0());
+    transcript.append_field(b"g1", &round_poly.eval_1());
+    for g in &round_poly.evals[2..] {
+        transcript.append_field(b"gi", g);
+    }
+}
 
-/// Generate a sumcheck proof
+/// Generate a sumcheck proof with the default (statement-binding) configuration
 /// 
 /// # Arguments
 /// * `stmt` - Public statement containing n_vars and claimed sum
-/// * `poly` - The multilinear polynomial to prove
+/// * `poly` - The multilinear polynomial to prove, in any representation
 /// * `transcript` - Fiat-Shamir transcript for challenge generation
 /// 
 /// # Returns
 /// A `SumcheckProof` containing one round polynomial per variable
-pub fn prove<F: PrimeField>(
+pub fn prove<F: Field, P: MultilinearPolynomial<F>>(
     stmt: &Statement<F>,
-    poly: &MLPoly<F>,
+    poly: &P,
     transcript: &mut Transcript,
 ) -> SumcheckProof<F> {
-    let mut current_poly = poly.clone();
+    prove_with_config(stmt, poly, transcript, &SumcheckConfig::default())
+}
+
+/// Generate a sumcheck proof with explicit transcript options
+///
+/// The verifier must use the same `config`.
+pub fn prove_with_config<F: Field, P: MultilinearPolynomial<F>>(
+    stmt: &Statement<F>,
+    poly: &P,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> SumcheckProof<F> {
+    prove_with_output(stmt, poly, transcript, config).proof
+}
+
+/// Generate a sumcheck proof and keep the prover's view of the run
+///
+/// Besides the proof this returns the challenge point r and f(r), which a
+/// prover needs to open a commitment to `poly` for the verifier's final check.
+/// The prover runs on the representation itself, e.g. in O(nnz) per round for
+/// a `SparseMLPoly`; the proof is the same as for its dense evaluations.
+/// `poly` is left untouched, so binding x_1 allocates a folded copy of half
+/// the size; use `prove_owned` or `prove_in_place` to avoid that.
+///
+/// # Panics
+/// Panics if `poly` does not have `stmt.n_vars` variables
+pub fn prove_with_output<F: Field, P: MultilinearPolynomial<F>>(
+    stmt: &Statement<F>,
+    poly: &P,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> ProverOutput<F> {
+    assert_eq!(
+        poly.n_vars(),
+        stmt.n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, config, transcript);
+
+    let mut round_polys = Vec::with_capacity(stmt.n_vars);
+    let mut point = Vec::with_capacity(stmt.n_vars);
+    if stmt.n_vars == 0 {
+        return ProverOutput {
+            proof: SumcheckProof { round_polys },
+            point,
+            final_evals: vec![poly.eval_at(&[])],
+        };
+    }
+
+    // The first fold may change representation; later ones keep `P::Folded`
+    let order = config.binding_order;
+    let round_poly = {
+        let (g0, g1) = round_sums(poly, order);
+        RoundPoly::new(g0, g1)
+    };
+    append_round_poly(&round_poly, transcript);
+    round_polys.push(round_poly);
+
+    let r: F = transcript.challenge_scalar(b"r");
+    point.push(r);
+    let mut folded = match order {
+        BindingOrder::LowToHigh => poly.fold_first_var(r),
+        BindingOrder::HighToLow => poly.fold_last_var(r),
+    };
+    run_rounds(&mut folded, order, transcript, &mut round_polys, &mut point);
+    to_variable_order(&mut point, order);
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![folded.eval_at(&[])],
+    }
+}
+
+/// Generate a sumcheck proof, consuming the polynomial
+///
+/// Same proof as `prove_with_output`; the evaluation vector is folded in place,
+/// so peak memory is the input itself.
+pub fn prove_owned<F: Field>(
+    stmt: &Statement<F>,
+    mut poly: MLPoly<F>,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> ProverOutput<F> {
+    prove_in_place(stmt, &mut poly, transcript, config)
+}
+
+/// Generate a sumcheck proof, folding the caller's polynomial in place
+///
+/// No memory is allocated for the polynomial. On return `poly` is the constant
+/// f(r_1, ..., r_n); its buffer keeps the original capacity and can be reused.
+///
+/// # Panics
+/// Panics if `poly` does not have `stmt.n_vars` variables
+pub fn prove_in_place<F: Field>(
+    stmt: &Statement<F>,
+    poly: &mut MLPoly<F>,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> ProverOutput<F> {
+    assert_eq!(
+        poly.n_vars, stmt.n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, config, transcript);
+
     let mut round_polys = Vec::with_capacity(stmt.n_vars);
+    let mut point = Vec::with_capacity(stmt.n_vars);
+    run_rounds(poly, config.binding_order, transcript, &mut round_polys, &mut point);
+    to_variable_order(&mut point, config.binding_order);
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![poly.evals[0]],
+    }
+}
+
+/// Generate a sumcheck proof for a polynomial with small integer evaluations
+///
+/// Round 1 is summed over the integers and x_1 is bound directly from the
+/// integer table, so field arithmetic starts on a table of half the size.
+/// With `BindingOrder::HighToLow` the table is lifted to F up front instead.
+///
+/// # Returns
+/// The same proof, challenge point and final evaluation as `prove_with_output`
+/// on `poly.to_mlpoly()`
+///
+/// # Panics
+/// Panics if `poly` does not have `stmt.n_vars` variables
+pub fn prove_small<F: Field, T: SmallValue>(
+    stmt: &Statement<F>,
+    poly: &SmallMLPoly<T>,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> ProverOutput<F> {
+    if poly.n_vars == 0 || config.binding_order == BindingOrder::HighToLow {
+        return prove_owned(stmt, poly.to_mlpoly(), transcript, config);
+    }
+    assert_eq!(
+        poly.n_vars, stmt.n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, config, transcript);
+
+    let mut round_polys = Vec::with_capacity(stmt.n_vars);
+    let mut point = Vec::with_capacity(stmt.n_vars);
+
+    let (g0, g1) = poly.round_sum_g0_g1();
+    let round_poly = RoundPoly::new(g0, g1);
+    append_round_poly(&round_poly, transcript);
+    round_polys.push(round_poly);
+
+    let r: F = transcript.challenge_scalar(b"r");
+    point.push(r);
+    let mut folded = poly.fold_first_var(r);
+    run_rounds(
+        &mut folded,
+        BindingOrder::LowToHigh,
+        transcript,
+        &mut round_polys,
+        &mut point,
+    );
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![folded.evals[0]],
+    }
+}
+
+/// (g(0), g(1)) for the round that binds the next variable in `order`
+fn round_sums<F: Field, P: MultilinearPolynomial<F>>(poly: &P, order: BindingOrder) -> (F, F) {
+    match order {
+        BindingOrder::LowToHigh => poly.round_sum_g0_g1(),
+        BindingOrder::HighToLow => poly.round_sum_last_var(),
+    }
+}
+
+/// Put challenges collected in binding order into variable order x_1, ..., x_n
+pub(crate) fn to_variable_order<F>(point: &mut [F], order: BindingOrder) {
+    if order == BindingOrder::HighToLow {
+        point.reverse();
+    }
+}
+
+/// Run the remaining rounds of the plain sumcheck on `poly`, folding it in place
+///
+/// Round polynomials and challenges are appended to `round_polys` and `point`,
+/// so a prover that handled earlier rounds by other means can finish here.
+/// Challenges are appended in binding order.
+pub(crate) fn run_rounds<F: Field, P: MultilinearPolynomial<F, Folded = P>>(
+    poly: &mut P,
+    order: BindingOrder,
+    transcript: &mut Transcript,
+    round_polys: &mut Vec<RoundPoly<F>>,
+    point: &mut Vec<F>,
+) {
+    // Only the first round needs a separate pass; later sums fall out of the previous fold
+    let sums = if poly.n_vars() > 0 {
+        round_sums(poly, order)
+    } else {
+        (F::ZERO, F::ZERO)
+    };
+    run_rounds_from(poly, sums, order, transcript, round_polys, point);
+}
 
-    for _ in 0..stmt.n_vars {
-        // 1. Compute round polynomial g_i(X) where g_i(0) + g_i(1) = current claim
-        let (g0, g1) = current_poly.round_sum_g0_g1();
+/// `run_rounds` with the first round's (g(0), g(1)) already computed by the caller
+pub(crate) fn run_rounds_from<F: Field, P: MultilinearPolynomial<F, Folded = P>>(
+    poly: &mut P,
+    mut sums: (F, F),
+    order: BindingOrder,
+    transcript: &mut Transcript,
+    round_polys: &mut Vec<RoundPoly<F>>,
+    point: &mut Vec<F>,
+) {
+    while poly.n_vars() > 0 {
+        // 1. Round polynomial g_i(X) where g_i(0) + g_i(1) = current claim
+        let (g0, g1) = sums;
         let round_poly = RoundPoly::new(g0, g1);
 
         // 2. Commit to round polynomial via transcript
-        transcript.append_field(b"g0", &g0);
-        transcript.append_field(b"g1", &g1);
+        append_round_poly(&round_poly, transcript);
         round_polys.push(round_poly);
 
         // 3. Get challenge from transcript (Fiat-Shamir)
         let r: F = transcript.challenge_scalar(b"r");
-
-        // 4. Fold polynomial: f'(x_2, ..., x_n) = f(r, x_2, ..., x_n)
-        current_poly = current_poly.fold_first_var(r);
+        point.push(r);
+
+        // 4. Fold polynomial: bind the next variable to r, and sum the halves of
+        //    the result for the next round in the same pass
+        match (order, poly.n_vars() >= 2) {
+            (BindingOrder::LowToHigh, true) => sums = poly.fold_and_round_sum(r),
+            (BindingOrder::LowToHigh, false) => poly.fold_first_var_in_place(r),
+            (BindingOrder::HighToLow, true) => sums = poly.fold_last_and_round_sum(r),
+            (BindingOrder::HighToLow, false) => poly.fold_last_var_in_place(r),
+        }
     }
-
-    SumcheckProof { round_polys }
 }
 
-/// Verify a sumcheck proof
+/// Verify a sumcheck proof with the default (statement-binding) configuration
 /// 
 /// # Arguments
 /// * `stmt` - Public statement containing n_vars and claimed sum
@@ -56,22 +306,68 @@ pub fn prove<F: PrimeField>(
 /// # Returns
 /// * `Ok(true)` if the proof is valid
 /// * `Ok(false)` if the final oracle check fails
-/// * `Err(_)` if a round check fails
-pub fn verify<F: PrimeField, O: Oracle<F>>(
+/// * `Err(_)` if a round check fails or the oracle cannot answer
+pub fn verify<F: Field, O: Oracle<F>>(
     stmt: &Statement<F>,
     proof: &SumcheckProof<F>,
     oracle: &O,
     transcript: &mut Transcript,
 ) -> Result<bool> {
+    verify_with_config(stmt, proof, oracle, transcript, &SumcheckConfig::default())
+}
+
+/// Verify a sumcheck proof with explicit transcript options
+///
+/// `config` must match the one used by the prover.
+pub fn verify_with_config<F: Field, O: Oracle<F>>(
+    stmt: &Statement<F>,
+    proof: &SumcheckProof<F>,
+    oracle: &O,
+    transcript: &mut Transcript,
+    config: &SumcheckConfig,
+) -> Result<bool> {
+    absorb_statement(stmt, config, transcript);
+    let mut subclaim = verify_rounds(stmt.claim_sum, stmt.n_vars, 1, proof, transcript)?;
+    to_variable_order(&mut subclaim.point, config.binding_order);
+
+    // Final check: oracle(r_1, ..., r_n) == final claim
+    let oracle_eval = oracle.query(&subclaim.point)?;
+    Ok(oracle_eval == subclaim.expected_eval)
+}
+
+/// Run the round checks of a degree-`degree` sumcheck and return the final claim
+///
+/// This is the verifier core shared by every sumcheck variant; the caller is
+/// responsible for absorbing the statement beforehand and for checking the
+/// returned `SubClaim` against the summed polynomial. The subclaim's point is in
+/// binding order; a `HighToLow` caller reverses it into variable order.
+///
+/// # Returns
+/// * `Ok(subclaim)` if every round satisfies g(0) + g(1) == claim
+/// * `Err(_)` if `degree` is 0, the proof has the wrong shape or a round check fails
+pub fn verify_rounds<F: Field>(
+    claim_sum: F,
+    n_vars: usize,
+    degree: usize,
+    proof: &SumcheckProof<F>,
+    transcript: &mut Transcript,
+) -> Result<SubClaim<F>> {
+    // Every round polynomial needs g(0) and g(1)
+    if degree == 0 {
+        return Err(Error::DimensionMismatch("round polynomials must have degree at least 1"));
+    }
     // Check proof has correct number of rounds
-    if proof.num_rounds() != stmt.n_vars {
+    if proof.num_rounds() != n_vars {
         return Err(Error::DimensionMismatch("wrong number of round polynomials"));
     }
 
-    let mut claim = stmt.claim_sum;
-    let mut r_vec = Vec::with_capacity(stmt.n_vars);
+    let mut claim = claim_sum;
+    let mut r_vec = Vec::with_capacity(n_vars);
 
     for round_poly in &proof.round_polys {
+        if round_poly.evals.len() != degree + 1 {
+            return Err(Error::DimensionMismatch("wrong round polynomial degree"));
+        }
         let g0 = round_poly.eval_0();
         let g1 = round_poly.eval_1();
 
@@ -81,8 +377,7 @@ pub fn verify<F: PrimeField, O: Oracle<F>>(
         }
 
         // Replay transcript (must match prover)
-        transcript.append_field(b"g0", &g0);
-        transcript.append_field(b"g1", &g1);
+        append_round_poly(round_poly, transcript);
 
         // Derive same challenge as prover (Fiat-Shamir)
         let r: F = transcript.challenge_scalar(b"r");
@@ -92,9 +387,10 @@ pub fn verify<F: PrimeField, O: Oracle<F>>(
         claim = round_poly.eval(r);
     }
 
-    // Final check: oracle(r_1, ..., r_n) == final claim
-    let oracle_eval = oracle.query(&r_vec);
-    Ok(oracle_eval == claim)
+    Ok(SubClaim {
+        point: r_vec,
+        expected_eval: claim,
+    })
 }
 
 #[cfg(test)]
@@ -102,6 +398,7 @@ mod tests {
     use super::*;
     use crate::oracle::PolyOracle;
     use ark_bn254::Fr;
+    use ark_ff::Field;
     use ark_std::UniformRand;
 
     #[test]
@@ -186,6 +483,277 @@ mod tests {
         println!("Proof size for {} vars: {} bytes", n_vars, bytes.len());
     }
 
+    #[test]
+    fn test_proof_matches_serial_reference() {
+        // Replays the protocol with plain loops over the evaluation vector; under the
+        // `parallel` feature this checks that the rayon prover emits the same bytes
+        use ark_serialize::CanonicalSerialize;
+
+        let mut rng = ark_std::test_rng();
+        let n_vars = 10;
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly::from_evals(evals.clone());
+        let stmt = Statement {
+            n_vars,
+            claim_sum: evals.iter().sum(),
+        };
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let proof = prove(&stmt, &poly, &mut transcript);
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        absorb_statement(&stmt, &SumcheckConfig::default(), &mut transcript);
+        let mut cur = evals;
+        let mut expected = Vec::with_capacity(n_vars);
+        for _ in 0..n_vars {
+            let (mut g0, mut g1) = (Fr::ZERO, Fr::ZERO);
+            for pair in cur.chunks(2) {
+                g0 += pair[0];
+                g1 += pair[1];
+            }
+            let round_poly = RoundPoly::new(g0, g1);
+            append_round_poly(&round_poly, &mut transcript);
+            expected.push(round_poly);
+            let r: Fr = transcript.challenge_scalar(b"r");
+            cur = cur
+                .chunks(2)
+                .map(|pair| pair[0] + r * (pair[1] - pair[0]))
+                .collect();
+        }
+
+        let mut proof_bytes = Vec::new();
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut expected_bytes = Vec::new();
+        SumcheckProof { round_polys: expected }
+            .serialize_compressed(&mut expected_bytes)
+            .unwrap();
+        assert_eq!(proof_bytes, expected_bytes);
+    }
+
+    fn round_evals(proof: &SumcheckProof<Fr>) -> Vec<Vec<Fr>> {
+        proof.round_polys.iter().map(|g| g.evals.clone()).collect()
+    }
+
+    #[test]
+    fn test_owned_and_in_place_provers_match() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 6;
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly::from_evals(evals);
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let config = SumcheckConfig::default();
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let reference = prove_with_output(&stmt, &poly, &mut transcript, &config);
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let owned = prove_owned(&stmt, poly.clone(), &mut transcript, &config);
+        assert_eq!(round_evals(&owned.proof), round_evals(&reference.proof));
+        assert_eq!(owned.point, reference.point);
+        assert_eq!(owned.final_evals, reference.final_evals);
+
+        let mut working = poly.clone();
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let in_place = prove_in_place(&stmt, &mut working, &mut transcript, &config);
+        assert_eq!(round_evals(&in_place.proof), round_evals(&reference.proof));
+        assert!(working.is_constant());
+        assert_eq!(working.evals[0], poly.eval_at(&in_place.point));
+    }
+
+    #[test]
+    fn test_in_place_prover_keeps_capacity() {
+        // Checked in both builds; with `parallel` this pins the rayon folds to the
+        // caller's buffer as well
+        let mut rng = ark_std::test_rng();
+        let n_vars = 12;
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly::from_evals(evals);
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+
+        for order in [BindingOrder::LowToHigh, BindingOrder::HighToLow] {
+            let config = SumcheckConfig::default().with_binding_order(order);
+            let mut working = poly.clone();
+            let capacity = working.evals.capacity();
+            let ptr = working.evals.as_ptr();
+
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            prove_in_place(&stmt, &mut working, &mut transcript, &config);
+            assert!(working.is_constant());
+            assert_eq!(working.evals.capacity(), capacity, "buffer should be reused");
+            assert_eq!(working.evals.as_ptr(), ptr, "buffer should not be replaced");
+        }
+    }
+
+    #[test]
+    fn test_small_prover_matches_lifted() {
+        let mut rng = ark_std::test_rng();
+        let config = SumcheckConfig::default();
+        for n_vars in [0, 1, 7] {
+            let bytes: Vec<u8> = (0..(1 << n_vars)).map(|_| u8::rand(&mut rng)).collect();
+            let small = SmallMLPoly::from_evals(bytes);
+            let lifted: MLPoly<Fr> = small.to_mlpoly();
+            let stmt = Statement {
+                n_vars,
+                claim_sum: small.sum_all(),
+            };
+
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let reference = prove_with_output(&stmt, &lifted, &mut transcript, &config);
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let out = prove_small(&stmt, &small, &mut transcript, &config);
+
+            assert_eq!(round_evals(&out.proof), round_evals(&reference.proof));
+            assert_eq!(out.point, reference.point);
+            assert_eq!(out.final_evals, reference.final_evals);
+        }
+    }
+
+    #[test]
+    fn test_sparse_prover_matches_dense() {
+        use mlpoly::SparseMLPoly;
+
+        let mut rng = ark_std::test_rng();
+        let n_vars = 8;
+        // A few isolated nonzeros, including adjacent and lone entries
+        let entries = (0..12)
+            .map(|k| ((k * 37) % (1 << n_vars), Fr::rand(&mut rng)))
+            .collect();
+        let sparse = SparseMLPoly::new(n_vars, entries);
+        let dense = sparse.to_dense();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: sparse.sum_all(),
+        };
+        let config = SumcheckConfig::default();
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let reference = prove_with_output(&stmt, &dense, &mut transcript, &config);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let out = prove_with_output(&stmt, &sparse, &mut transcript, &config);
+
+        assert_eq!(round_evals(&out.proof), round_evals(&reference.proof));
+        assert_eq!(out.point, reference.point);
+        assert_eq!(out.final_evals, reference.final_evals);
+
+        let oracle = PolyOracle::new(sparse);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let result = verify_with_config(&stmt, &out.proof, &oracle, &mut transcript, &config);
+        assert!(result.unwrap(), "sparse oracle should accept the proof");
+    }
+
+    #[test]
+    fn test_lazy_prover_matches_dense() {
+        use mlpoly::LazyMLPoly;
+
+        let n_vars = 7;
+        let lazy = LazyMLPoly::new(n_vars, |i: usize| Fr::from((i as u64) * 3 + 1));
+        let dense: MLPoly<Fr> = lazy.to_dense();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: dense.sum_all(),
+        };
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let reference = prove(&stmt, &dense, &mut transcript);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let proof = prove(&stmt, &lazy, &mut transcript);
+        assert_eq!(round_evals(&proof), round_evals(&reference));
+
+        let oracle = PolyOracle::new(lazy);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        assert!(verify(&stmt, &proof, &oracle, &mut transcript).unwrap());
+    }
+
+    #[test]
+    fn test_high_to_low_binding() {
+        use mlpoly::SparseMLPoly;
+
+        let mut rng = ark_std::test_rng();
+        let config = SumcheckConfig::default().with_binding_order(BindingOrder::HighToLow);
+        for n_vars in [1, 2, 6] {
+            let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+            let poly = MLPoly::from_evals(evals);
+            let stmt = Statement {
+                n_vars,
+                claim_sum: poly.sum_all(),
+            };
+
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let out = prove_with_output(&stmt, &poly, &mut transcript, &config);
+            // Round 1 splits on x_n, and the point comes back as (r_1, ..., r_n)
+            assert_eq!(out.proof.round_polys[0].evals, {
+                let (g0, g1) = poly.round_sum_last_var();
+                vec![g0, g1]
+            });
+            assert_eq!(out.final_evals, vec![poly.eval_at(&out.point)]);
+
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let in_place = prove_in_place(&stmt, &mut poly.clone(), &mut transcript, &config);
+            assert_eq!(round_evals(&in_place.proof), round_evals(&out.proof));
+            assert_eq!(in_place.point, out.point);
+
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let sparse = SparseMLPoly::from_dense(&poly);
+            let from_sparse = prove_with_output(&stmt, &sparse, &mut transcript, &config);
+            assert_eq!(round_evals(&from_sparse.proof), round_evals(&out.proof));
+
+            let oracle = PolyOracle::new(poly);
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let result = verify_with_config(&stmt, &out.proof, &oracle, &mut transcript, &config);
+            assert!(result.unwrap(), "high-to-low proof should verify");
+        }
+    }
+
+    #[test]
+    fn test_high_to_low_is_low_to_high_on_reversed_variables() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 5;
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly::from_evals(evals);
+        let reversed = poly.permute_vars(&[4, 3, 2, 1, 0]);
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let high_to_low = SumcheckConfig::default().with_binding_order(BindingOrder::HighToLow);
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let out = prove_with_output(&stmt, &poly, &mut transcript, &high_to_low);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let low_to_high = SumcheckConfig::default();
+        let reference = prove_with_output(&stmt, &reversed, &mut transcript, &low_to_high);
+
+        assert_eq!(round_evals(&out.proof), round_evals(&reference.proof));
+        let mut point = reference.point.clone();
+        point.reverse();
+        assert_eq!(out.point, point);
+
+        // A verifier expecting the other order queries the wrong point
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let result = verify(&stmt, &out.proof, &oracle, &mut transcript);
+        assert!(!result.unwrap(), "binding order mismatch should be rejected");
+    }
+
+    #[test]
+    fn test_degree_zero_rejected() {
+        // A one-evaluation round polynomial has no g(1) to read
+        let proof = SumcheckProof {
+            round_polys: vec![RoundPoly {
+                evals: vec![Fr::from(7u64)],
+            }],
+        };
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let result = verify_rounds(Fr::from(7u64), 1, 0, &proof, &mut transcript);
+        assert!(matches!(result, Err(Error::DimensionMismatch(_))));
+    }
+
     #[test]
     fn test_single_variable() {
         let mut rng = ark_std::test_rng();
@@ -205,5 +773,135 @@ mod tests {
 
         assert!(result.unwrap());
     }
-}
 
+    /// Malicious prover that never looks at a polynomial: it picks round
+    /// polynomials for `claim` first and only afterwards builds a polynomial
+    /// that passes the final oracle check at the derived challenges.
+    fn forge_adaptive(
+        n_vars: usize,
+        claim: Fr,
+        transcript: &mut Transcript,
+    ) -> (SumcheckProof<Fr>, MLPoly<Fr>) {
+        let mut rng = ark_std::test_rng();
+        let mut cur = claim;
+        let mut r_vec = Vec::with_capacity(n_vars);
+        let mut round_polys = Vec::with_capacity(n_vars);
+        for _ in 0..n_vars {
+            let g0 = Fr::rand(&mut rng);
+            let g1 = cur - g0;
+            transcript.append_field(b"g0", &g0);
+            transcript.append_field(b"g1", &g1);
+            let r: Fr = transcript.challenge_scalar(b"r");
+            let round_poly = RoundPoly::new(g0, g1);
+            cur = round_poly.eval(r);
+            round_polys.push(round_poly);
+            r_vec.push(r);
+        }
+
+        // Patch evals[0] so that f(r) == cur; eq(r, 0) = ∏ (1 - r_i)
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let mut poly = MLPoly { n_vars, evals };
+        let eq_r_0: Fr = r_vec.iter().map(|r| Fr::from(1u64) - r).product();
+        let delta = (cur - poly.eval_at(&r_vec)) * eq_r_0.inverse().unwrap();
+        poly.evals[0] += delta;
+
+        (SumcheckProof { round_polys }, poly)
+    }
+
+    #[test]
+    fn test_adaptive_forgery_without_statement_binding() {
+        let n_vars = 3;
+        let claim = Fr::from(42u64);
+
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let (proof, poly) = forge_adaptive(n_vars, claim, &mut transcript);
+        assert_ne!(poly.sum_all(), claim, "forged claim must be false");
+
+        let stmt = Statement { n_vars, claim_sum: claim };
+        let config = SumcheckConfig::unbound();
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let result = verify_with_config(&stmt, &proof, &oracle, &mut transcript, &config);
+
+        assert!(result.unwrap(), "unbound transcript accepts the forgery");
+    }
+
+    #[test]
+    fn test_adaptive_forgery_rejected_with_statement_binding() {
+        let n_vars = 3;
+        let public_input = b"instance".to_vec();
+        let bound = SumcheckConfig::with_public_input(public_input.clone());
+        let unbound = SumcheckConfig {
+            public_input,
+            ..SumcheckConfig::unbound()
+        };
+
+        for (config, accepted) in [(unbound, true), (bound, false)] {
+            // The forger starts the transcript for a claim of 0...
+            let committed = Statement {
+                n_vars,
+                claim_sum: Fr::from(0u64),
+            };
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            absorb_statement(&committed, &config, &mut transcript);
+            let claim = Fr::from(42u64);
+            let (proof, poly) = forge_adaptive(n_vars, claim, &mut transcript);
+            assert_ne!(poly.sum_all(), claim, "forged claim must be false");
+
+            // ...and, having seen every challenge, presents the proof for 42
+            // with the same public input
+            let stmt = Statement {
+                n_vars,
+                claim_sum: claim,
+            };
+            let oracle = PolyOracle::new(poly);
+            let mut transcript = Transcript::new(b"sumcheck-test");
+            let result = verify_with_config(&stmt, &proof, &oracle, &mut transcript, &config);
+            assert_eq!(result == Ok(true), accepted, "bind_statement: {}", config.bind_statement);
+        }
+    }
+
+    #[test]
+    fn test_proof_bound_to_public_input() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 3;
+
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let poly = MLPoly { n_vars, evals };
+        let stmt = Statement { n_vars, claim_sum: poly.sum_all() };
+
+        let config = SumcheckConfig::with_public_input(b"instance-a".to_vec());
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let proof = prove_with_config(&stmt, &poly, &mut transcript, &config);
+
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        assert!(verify_with_config(&stmt, &proof, &oracle, &mut transcript, &config).unwrap());
+
+        let other = SumcheckConfig::with_public_input(b"instance-b".to_vec());
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        let result = verify_with_config(&stmt, &proof, &oracle, &mut transcript, &other);
+        assert_ne!(result, Ok(true));
+    }
+
+    #[test]
+    fn test_proof_for_one_claim_rejected_for_another() {
+        let n_vars = 3;
+        let claim = Fr::from(42u64);
+        let stmt = Statement { n_vars, claim_sum: claim };
+
+        // Forgery that is internally consistent for `claim` under binding...
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        absorb_statement(&stmt, &SumcheckConfig::default(), &mut transcript);
+        let (mut proof, poly) = forge_adaptive(n_vars, claim, &mut transcript);
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        assert!(verify(&stmt, &proof, &oracle, &mut transcript).unwrap());
+
+        // ...cannot be retargeted by shifting g(0) to match another claim
+        let other = Statement { n_vars, claim_sum: claim + Fr::from(1u64) };
+        proof.round_polys[0].evals[0] += Fr::from(1u64);
+        let mut transcript = Transcript::new(b"sumcheck-test");
+        assert_ne!(verify(&other, &proof, &oracle, &mut transcript), Ok(true));
+    }
+}
@@ -0,0 +1,534 @@
+//! Spartan: proving R1CS satisfiability with two sumchecks
+//!
+//! An R1CS instance is satisfied by z = (1, io, w) when Az ∘ Bz = Cz. Here z is
+//! laid out as a table of 2^s entries whose lower half holds (1, io) (zero-padded)
+//! and whose upper half holds the witness w, so that
+//! z̃(y, y_s) = (1 − y_s) · (1, io)~(y) + y_s · w̃(y)
+//! and the verifier only needs an oracle for w̃.
+//!
+//! 1. Outer sumcheck: a zerocheck of Az ∘ Bz − Cz over the constraints, i.e.
+//!    ∑_x eq(τ, x) · ((Az)(x) · (Bz)(x) − (Cz)(x)) = 0, ending at a point r_x with
+//!    claimed values of Az, Bz and Cz there.
+//! 2. Inner sumcheck: the three claims are combined with random ρ_A, ρ_B, ρ_C
+//!    into ∑_y (ρ_A Ã + ρ_B B̃ + ρ_C C̃)(r_x, y) · z̃(y), which the verifier checks
+//!    by evaluating the sparse matrices itself and querying w̃ once. The matrix
+//!    evaluation builds the eq tables of r_x and y, so it costs
+//!    O(2^{s_x} + 2^{s_y} + nnz) for s_x constraint and s_y z variables.
+//!
+//! Fiat-Shamir: τ and every later challenge depend on the instance, the public
+//! inputs and a commitment to w̃, which both sides absorb first. The verifier
+//! takes the witness as a `CommittedOracle` and absorbs that oracle's own
+//! commitment, so the opening is checked against exactly what was bound;
+//! without the binding a prover could pick a non-satisfying w after seeing τ
+//! that still makes the outer sum vanish.
+
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use mlpoly::{eq_eval, MLPoly};
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::pcs::{CommittedOracle, PolynomialCommitment};
+use crate::product::{prove_product, verify_product};
+use crate::transcript::Transcript;
+use crate::types::{SpartanProof, SpartanProverOutput, Statement};
+use crate::virtual_poly::{Composition, VirtualPoly};
+use crate::zerocheck::{prove_zerocheck, verify_zerocheck_rounds};
+
+/// Sparse linear combination of z: (column, coefficient) pairs
+pub type LinearCombination<F> = Vec<(usize, F)>;
+
+/// Rank-1 constraint system: (A z) ∘ (B z) = C z
+///
+/// Columns of z are numbered 0 for the constant 1, then 1, ..., `num_inputs`
+/// for the public inputs and `num_inputs + 1`, ... for the witness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1CS<F: PrimeField> {
+    /// Number of public inputs (not counting the constant 1)
+    pub num_inputs: usize,
+    /// Number of witness entries
+    pub num_witness: usize,
+    /// Nonzero entries (row, column, value) of each matrix
+    pub a: Vec<(usize, usize, F)>,
+    pub b: Vec<(usize, usize, F)>,
+    pub c: Vec<(usize, usize, F)>,
+    /// Number of constraints (rows)
+    pub num_cons: usize,
+}
+
+impl<F: PrimeField> R1CS<F> {
+    /// Create an instance with no constraints yet
+    pub fn new(num_inputs: usize, num_witness: usize) -> Self {
+        Self {
+            num_inputs,
+            num_witness,
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+            num_cons: 0,
+        }
+    }
+
+    /// Add the constraint ⟨a, z⟩ · ⟨b, z⟩ = ⟨c, z⟩
+    ///
+    /// # Panics
+    /// Panics if a column is out of range
+    pub fn add_constraint(
+        &mut self,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+    ) {
+        let row = self.num_cons;
+        let num_cols = 1 + self.num_inputs + self.num_witness;
+        for (lc, matrix) in [(a, &mut self.a), (b, &mut self.b), (c, &mut self.c)] {
+            for (col, value) in lc {
+                assert!(col < num_cols, "column {} out of range", col);
+                matrix.push((row, col, value));
+            }
+        }
+        self.num_cons += 1;
+    }
+
+    /// Number of variables of the MLEs of Az, Bz and Cz
+    pub fn constraint_vars(&self) -> usize {
+        self.num_cons.max(1).next_power_of_two().trailing_zeros() as usize
+    }
+
+    /// Number of variables of z̃; the witness MLE has one fewer
+    pub fn z_vars(&self) -> usize {
+        let half = (1 + self.num_inputs)
+            .max(self.num_witness)
+            .next_power_of_two();
+        1 + half.trailing_zeros() as usize
+    }
+
+    /// Position of column `col` in the table of z
+    fn z_index(&self, col: usize) -> usize {
+        if col <= self.num_inputs {
+            col
+        } else {
+            (1 << (self.z_vars() - 1)) + col - self.num_inputs - 1
+        }
+    }
+
+    /// The table of z: (1, io) in the lower half, the witness in the upper half
+    fn z(&self, inputs: &[F], witness: &[F]) -> MLPoly<F> {
+        let mut z = MLPoly::zero(self.z_vars());
+        z.evals[0] = F::ONE;
+        z.evals[1..=inputs.len()].copy_from_slice(inputs);
+        let half = z.len() / 2;
+        z.evals[half..half + witness.len()].copy_from_slice(witness);
+        z
+    }
+
+    /// M z for one of the matrices, padded to 2^constraint_vars rows
+    fn mat_vec(&self, matrix: &[(usize, usize, F)], z: &MLPoly<F>) -> MLPoly<F> {
+        let mut out = MLPoly::zero(self.constraint_vars());
+        for &(row, col, value) in matrix {
+            out.evals[row] += value * z.evals[self.z_index(col)];
+        }
+        out
+    }
+
+    /// Check (A z) ∘ (B z) = (C z) directly
+    ///
+    /// # Panics
+    /// Panics if `inputs` or `witness` have the wrong length
+    pub fn is_satisfied(&self, inputs: &[F], witness: &[F]) -> bool {
+        assert_eq!(inputs.len(), self.num_inputs, "wrong number of inputs");
+        assert_eq!(witness.len(), self.num_witness, "wrong witness length");
+        let z = self.z(inputs, witness);
+        let (az, bz, cz) = (
+            self.mat_vec(&self.a, &z),
+            self.mat_vec(&self.b, &z),
+            self.mat_vec(&self.c, &z),
+        );
+        (0..self.num_cons).all(|i| az.evals[i] * bz.evals[i] == cz.evals[i])
+    }
+
+    /// Absorb the instance, its public inputs and the witness commitment
+    fn absorb<C: CanonicalSerialize>(
+        &self,
+        inputs: &[F],
+        commitment: &C,
+        transcript: &mut Transcript,
+    ) {
+        transcript.append_message(b"protocol", b"spartan");
+        transcript.append_u64(b"num_cons", self.num_cons as u64);
+        transcript.append_u64(b"num_inputs", self.num_inputs as u64);
+        transcript.append_u64(b"num_witness", self.num_witness as u64);
+        for matrix in [&self.a, &self.b, &self.c] {
+            transcript.append_u64(b"nnz", matrix.len() as u64);
+            for (row, col, value) in matrix {
+                transcript.append_u64(b"row", *row as u64);
+                transcript.append_u64(b"col", *col as u64);
+                transcript.append_field(b"value", value);
+            }
+        }
+        for x in inputs {
+            transcript.append_field(b"input", x);
+        }
+        transcript.append_serializable(b"witness_commitment", commitment);
+    }
+}
+
+/// Az · Bz − Cz over the list [Az, Bz, Cz]
+fn outer_composition<F: PrimeField>() -> Composition<F> {
+    Composition {
+        num_mles: 3,
+        terms: vec![(F::ONE, vec![0, 1]), (-F::ONE, vec![2])],
+    }
+}
+
+/// Absorb the claimed values at r_x and draw ρ_A, ρ_B, ρ_C
+fn combine_claims<F: PrimeField>(evals: [F; 3], transcript: &mut Transcript) -> [F; 3] {
+    for v in &evals {
+        transcript.append_field(b"matrix_eval", v);
+    }
+    [(); 3].map(|_| transcript.challenge_scalar(b"rho"))
+}
+
+/// Oracle for y ↦ ∑_M ρ_M · M̃(r_x, y), evaluated from the sparse entries
+struct MatrixOracle<'a, F: PrimeField> {
+    instance: &'a R1CS<F>,
+    r_x: Vec<F>,
+    rho: [F; 3],
+}
+
+impl<F: PrimeField> Oracle<F> for MatrixOracle<'_, F> {
+    fn query(&self, y: &[F]) -> Result<F> {
+        let eq_x = MLPoly::eq_table(&self.r_x);
+        let eq_y = MLPoly::eq_table(y);
+        let instance = self.instance;
+        let mut sum = F::ZERO;
+        for (rho, matrix) in self.rho.iter().zip([&instance.a, &instance.b, &instance.c]) {
+            let eval: F = matrix
+                .iter()
+                .map(|&(row, col, value)| {
+                    value * eq_x.evals[row] * eq_y.evals[instance.z_index(col)]
+                })
+                .sum();
+            sum += *rho * eval;
+        }
+        Ok(sum)
+    }
+}
+
+/// Oracle for z̃, combining the public half with an oracle for the witness half
+struct ZOracle<'a, F: PrimeField> {
+    public: MLPoly<F>,
+    witness: &'a dyn Oracle<F>,
+}
+
+impl<F: PrimeField> Oracle<F> for ZOracle<'_, F> {
+    fn query(&self, y: &[F]) -> Result<F> {
+        let (&y_s, rest) = y.split_last().expect("z has at least one variable");
+        Ok((F::ONE - y_s) * self.public.eval_at(rest) + y_s * self.witness.query(rest)?)
+    }
+}
+
+/// Prove that `witness` satisfies `instance` for the public `inputs`
+///
+/// # Arguments
+/// * `instance` - The R1CS instance, known to the verifier
+/// * `inputs` - Public inputs
+/// * `witness` - Witness entries
+/// * `commitment` - Commitment to the witness MLE, bound before any challenge
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The proof, and the point and value of the final query to the witness MLE
+/// (what a commitment to the witness must be opened at)
+///
+/// # Panics
+/// Panics if `inputs` or `witness` have the wrong length
+pub fn prove_r1cs<F: PrimeField, C: CanonicalSerialize>(
+    instance: &R1CS<F>,
+    inputs: &[F],
+    witness: &[F],
+    commitment: &C,
+    transcript: &mut Transcript,
+) -> SpartanProverOutput<F> {
+    assert_eq!(inputs.len(), instance.num_inputs, "wrong number of inputs");
+    assert_eq!(witness.len(), instance.num_witness, "wrong witness length");
+    instance.absorb(inputs, commitment, transcript);
+
+    let z = instance.z(inputs, witness);
+    let az = instance.mat_vec(&instance.a, &z);
+    let bz = instance.mat_vec(&instance.b, &z);
+    let cz = instance.mat_vec(&instance.c, &z);
+
+    // Outer sumcheck: Az ∘ Bz − Cz vanishes on the constraints
+    let poly = VirtualPoly {
+        n_vars: instance.constraint_vars(),
+        composition: outer_composition(),
+        mles: vec![&az, &bz, &cz],
+    };
+    let outer = prove_zerocheck(&poly, transcript);
+    let [a_eval, b_eval, c_eval] = [0, 1, 2].map(|i| outer.final_evals[i]);
+    let rho = combine_claims([a_eval, b_eval, c_eval], transcript);
+
+    // Inner sumcheck: ∑_y m(y) · z(y) with m(y) = ∑_M ρ_M · M̃(r_x, y)
+    let eq_x = MLPoly::eq_table(&outer.point);
+    let mut m = MLPoly::zero(z.n_vars);
+    for (rho, matrix) in rho.iter().zip([&instance.a, &instance.b, &instance.c]) {
+        for &(row, col, value) in matrix {
+            m.evals[instance.z_index(col)] += *rho * eq_x.evals[row] * value;
+        }
+    }
+    let stmt = Statement {
+        n_vars: z.n_vars,
+        claim_sum: rho[0] * a_eval + rho[1] * b_eval + rho[2] * c_eval,
+    };
+    let inner = prove_product(&stmt, &[m, z], transcript);
+
+    let witness_point = inner.point[..inner.point.len() - 1].to_vec();
+    let mut padded = MLPoly::zero(witness_point.len());
+    padded.evals[..witness.len()].copy_from_slice(witness);
+    let witness_eval = padded.eval_at(&witness_point);
+
+    SpartanProverOutput {
+        proof: SpartanProof {
+            outer: outer.proof,
+            a_eval,
+            b_eval,
+            c_eval,
+            inner: inner.proof,
+        },
+        witness_point,
+        witness_eval,
+    }
+}
+
+/// Verify a Spartan proof for `instance` with public `inputs`
+///
+/// # Arguments
+/// * `instance` - The R1CS instance, as used by the prover
+/// * `inputs` - Public inputs
+/// * `proof` - The Spartan proof to verify
+/// * `witness_oracle` - Committed MLE of the witness (zero-padded to 2^(z_vars − 1)),
+///   opened at the prover's `witness_point`; its commitment is absorbed first
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final check of the inner sumcheck fails
+/// * `Err(_)` if the shapes disagree, a round check fails or the outer final check fails
+pub fn verify_r1cs<F: PrimeField, P: PolynomialCommitment<F>>(
+    instance: &R1CS<F>,
+    inputs: &[F],
+    proof: &SpartanProof<F>,
+    witness_oracle: &CommittedOracle<'_, F, P>,
+    transcript: &mut Transcript,
+) -> Result<bool>
+where
+    P::Commitment: CanonicalSerialize,
+{
+    if inputs.len() != instance.num_inputs {
+        return Err(Error::DimensionMismatch("wrong number of inputs"));
+    }
+    instance.absorb(inputs, &witness_oracle.commitment, transcript);
+
+    // Outer sumcheck, replaying `prove_zerocheck`
+    let s_x = instance.constraint_vars();
+    let (tau, subclaim) =
+        verify_zerocheck_rounds(s_x, &outer_composition(), &proof.outer, transcript)?;
+
+    let (a, b, c) = (proof.a_eval, proof.b_eval, proof.c_eval);
+    if eq_eval(&tau, &subclaim.point) * (a * b - c) != subclaim.expected_eval {
+        return Err(Error::InvalidProof("spartan outer check failed"));
+    }
+    let rho = combine_claims([a, b, c], transcript);
+
+    // Inner sumcheck, with both factors evaluated by the verifier
+    let mut public = MLPoly::zero(instance.z_vars() - 1);
+    public.evals[0] = F::ONE;
+    public.evals[1..=inputs.len()].copy_from_slice(inputs);
+    let matrices = MatrixOracle {
+        instance,
+        r_x: subclaim.point,
+        rho,
+    };
+    let z = ZOracle {
+        public,
+        witness: witness_oracle,
+    };
+    let stmt = Statement {
+        n_vars: instance.z_vars(),
+        claim_sum: rho[0] * a + rho[1] * b + rho[2] * c,
+    };
+    verify_product(&stmt, &proof.inner, &[&matrices, &z], transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcs::kzg::{KzgCommitment, KzgProverKey, KzgVerifierKey, MultilinearKzg};
+    use crate::zerocheck::sample_tau;
+    use ark_bn254::{Bn254, Fr};
+    use ark_ff::Field;
+    use ark_std::UniformRand;
+
+    /// x^3 + x + 5 = out, with public out and witness (x, x^2, x^3)
+    fn cubic() -> R1CS<Fr> {
+        let one = Fr::from(1u64);
+        let (out, x, x2, x3) = (1, 2, 3, 4);
+        let mut r1cs = R1CS::new(1, 3);
+        r1cs.add_constraint(vec![(x, one)], vec![(x, one)], vec![(x2, one)]);
+        r1cs.add_constraint(vec![(x2, one)], vec![(x, one)], vec![(x3, one)]);
+        r1cs.add_constraint(
+            vec![(x3, one), (x, one), (0, Fr::from(5u64))],
+            vec![(0, one)],
+            vec![(out, one)],
+        );
+        r1cs
+    }
+
+    type Kzg = MultilinearKzg<Bn254>;
+
+    /// Zero-padded MLE of the witness half of z
+    fn witness_poly(r1cs: &R1CS<Fr>, witness: &[Fr]) -> MLPoly<Fr> {
+        let mut w = MLPoly::zero(r1cs.z_vars() - 1);
+        w.evals[..witness.len()].copy_from_slice(witness);
+        w
+    }
+
+    fn keys(r1cs: &R1CS<Fr>) -> (KzgProverKey<Bn254>, KzgVerifierKey<Bn254>) {
+        Kzg::setup(r1cs.z_vars() - 1, &mut ark_std::test_rng())
+    }
+
+    /// Prove for `witness` with `committed` bound in the transcript, open
+    /// `committed` at the witness point and verify with `verifier_inputs`
+    fn run_with(
+        r1cs: &R1CS<Fr>,
+        inputs: &[Fr],
+        verifier_inputs: &[Fr],
+        witness: &[Fr],
+        committed: &MLPoly<Fr>,
+    ) -> Result<bool> {
+        let (pk, vk) = keys(r1cs);
+        let commitment = Kzg::commit(&pk, committed);
+        let mut transcript = Transcript::new(b"spartan-test");
+        let out = prove_r1cs(r1cs, inputs, witness, &commitment, &mut transcript);
+        assert_eq!(
+            witness_poly(r1cs, witness).eval_at(&out.witness_point),
+            out.witness_eval
+        );
+
+        let (value, proof) = Kzg::open(&pk, committed, &out.witness_point);
+        let oracle =
+            CommittedOracle::<Fr, Kzg>::new(&vk, commitment, out.witness_point, value, proof);
+        let mut transcript = Transcript::new(b"spartan-test");
+        verify_r1cs(r1cs, verifier_inputs, &out.proof, &oracle, &mut transcript)
+    }
+
+    fn run(r1cs: &R1CS<Fr>, inputs: &[Fr], witness: &[Fr]) -> Result<bool> {
+        run_with(r1cs, inputs, inputs, witness, &witness_poly(r1cs, witness))
+    }
+
+    /// Witness (2, 5, x3) with x3 solved so that the outer sum vanishes for the τ
+    /// drawn with `commitment`; it satisfies none of the constraints
+    fn forge_after_tau(
+        r1cs: &R1CS<Fr>,
+        inputs: &[Fr],
+        commitment: &KzgCommitment<Bn254>,
+    ) -> Vec<Fr> {
+        let mut transcript = Transcript::new(b"spartan-test");
+        r1cs.absorb(inputs, commitment, &mut transcript);
+        let tau: Vec<Fr> = sample_tau(r1cs.constraint_vars(), &mut transcript);
+        let eq = MLPoly::eq_table(&tau);
+        let outer_sum = |x3: Fr| {
+            let z = r1cs.z(inputs, &[Fr::from(2u64), Fr::from(5u64), x3]);
+            let (az, bz, cz) = (
+                r1cs.mat_vec(&r1cs.a, &z),
+                r1cs.mat_vec(&r1cs.b, &z),
+                r1cs.mat_vec(&r1cs.c, &z),
+            );
+            (0..eq.len())
+                .map(|i| eq.evals[i] * (az.evals[i] * bz.evals[i] - cz.evals[i]))
+                .sum::<Fr>()
+        };
+        // x3 enters every constraint linearly, so the sum is affine in x3
+        let (s0, s1) = (outer_sum(Fr::from(0u64)), outer_sum(Fr::from(1u64)));
+        let x3 = -s0 * (s1 - s0).inverse().unwrap();
+        vec![Fr::from(2u64), Fr::from(5u64), x3]
+    }
+
+    #[test]
+    fn test_spartan_honest_prover() {
+        let r1cs = cubic();
+        let inputs = [Fr::from(35u64)];
+        let witness: Vec<Fr> = [3u64, 9, 27].iter().map(|&v| Fr::from(v)).collect();
+        assert!(r1cs.is_satisfied(&inputs, &witness));
+        assert!(run(&r1cs, &inputs, &witness).unwrap());
+    }
+
+    #[test]
+    fn test_spartan_unsatisfied_witness_fails() {
+        let r1cs = cubic();
+        let inputs = [Fr::from(35u64)];
+        let witness: Vec<Fr> = [3u64, 9, 28].iter().map(|&v| Fr::from(v)).collect();
+        assert!(!r1cs.is_satisfied(&inputs, &witness));
+        let result = run(&r1cs, &inputs, &witness);
+        assert!(
+            !matches!(result, Ok(true)),
+            "bad witness should be rejected"
+        );
+
+        // Satisfying witness, but the verifier is given another public input
+        let witness: Vec<Fr> = [3u64, 9, 27].iter().map(|&v| Fr::from(v)).collect();
+        let poly = witness_poly(&r1cs, &witness);
+        let result = run_with(&r1cs, &inputs, &[Fr::from(36u64)], &witness, &poly);
+        assert!(
+            !matches!(result, Ok(true)),
+            "wrong public input should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_spartan_witness_chosen_after_tau_rejected() {
+        let r1cs = cubic();
+        let inputs = [Fr::from(35u64)];
+
+        // The forger has to commit before seeing τ. Solving for that τ changes
+        // the witness: the committed placeholder then opens to the wrong value,
+        // and committing to the new witness instead gives another τ
+        let placeholder = witness_poly(&r1cs, &[2u64, 5, 0].map(Fr::from));
+        let (pk, _) = keys(&r1cs);
+        let witness = forge_after_tau(&r1cs, &inputs, &Kzg::commit(&pk, &placeholder));
+        assert!(!r1cs.is_satisfied(&inputs, &witness));
+
+        let result = run_with(&r1cs, &inputs, &inputs, &witness, &placeholder);
+        assert!(
+            !matches!(result, Ok(true)),
+            "witness chosen after tau should be rejected"
+        );
+        let result = run(&r1cs, &inputs, &witness);
+        assert!(
+            !matches!(result, Ok(true)),
+            "witness chosen after tau should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_spartan_random_instance() {
+        // Chain of products w_{i+1} = w_i · (w_{i-1} + input), 11 constraints
+        let mut rng = ark_std::test_rng();
+        let num_witness = 13;
+        let one = Fr::from(1u64);
+        let mut r1cs = R1CS::new(1, num_witness);
+        let input = Fr::rand(&mut rng);
+        let mut witness = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        for i in 2..num_witness {
+            witness.push(witness[i - 1] * (witness[i - 2] + input));
+            r1cs.add_constraint(
+                vec![(i + 1, one)],
+                vec![(i, one), (1, one)],
+                vec![(i + 2, one)],
+            );
+        }
+        assert!(r1cs.is_satisfied(&[input], &witness));
+        assert!(run(&r1cs, &[input], &witness).unwrap());
+    }
+}
@@ -0,0 +1,374 @@
+//! Small-space sumcheck prover for polynomials that are only available as a stream
+//!
+//! The prover never holds the 2^n evaluations. In round i + 1 (with challenges
+//! r_1, ..., r_i fixed) the round sums are
+//! g(t) = ∑_{x_{≤i}, x_{>i+1}} eq((r_1, ..., r_i), x_{≤i}) · f(x_{≤i}, t, x_{>i+1}),
+//! which one sequential pass over the evaluations computes with a table of the
+//! 2^i weights eq(r, ·). After k = ⌊n/2⌋ such rounds a final pass materializes
+//! f(r_1, ..., r_k, ·), of size 2^{n-k}, and the remaining rounds run in memory.
+//! Peak memory is O(2^{⌈n/2⌉}) field elements at the cost of k + 1 passes.
+//!
+//! Memory-mapped files (`MmapMLPoly`) are a source too; `prove_mapped` is the
+//! faster alternative when half of the file fits in memory. Reading a source can
+//! fail (a corrupt element in a mapped file), so the provers return `io::Result`.
+//!
+//! The proof is identical to the one produced by `prove` on the same evaluations.
+
+use std::io;
+
+use ark_ff::PrimeField;
+use mlpoly::{MLPoly, MmapMLPoly};
+
+use crate::protocol::{absorb_statement, append_round_poly, run_rounds, run_rounds_from};
+use crate::transcript::Transcript;
+use crate::types::{
+    BindingOrder, ProverOutput, RoundPoly, Statement, SumcheckConfig, SumcheckProof,
+};
+
+/// A multilinear polynomial given by a pass over its evaluations
+///
+/// Evaluations are visited in index order, using the same LSB-first indexing as
+/// `MLPoly::evals`. Every call must visit the same 2^n values.
+pub trait EvalSource<F: PrimeField> {
+    /// Number of variables n
+    fn n_vars(&self) -> usize;
+
+    /// Call `visit` on f(0), f(1), ..., f(2^n - 1) in order, stopping at the
+    /// first evaluation that cannot be read
+    fn visit_evals(&self, visit: &mut dyn FnMut(F)) -> io::Result<()>;
+}
+
+impl<F: PrimeField> EvalSource<F> for MLPoly<F> {
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn visit_evals(&self, visit: &mut dyn FnMut(F)) -> io::Result<()> {
+        self.evals.iter().for_each(|&e| visit(e));
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> EvalSource<F> for MmapMLPoly<F> {
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn visit_evals(&self, visit: &mut dyn FnMut(F)) -> io::Result<()> {
+        self.iter().try_for_each(|e| e.map(&mut *visit))
+    }
+}
+
+/// Evaluations produced on demand by a generator `index -> f(index)`
+pub struct FnSource<G> {
+    pub n_vars: usize,
+    pub generator: G,
+}
+
+impl<G> FnSource<G> {
+    pub fn new(n_vars: usize, generator: G) -> Self {
+        Self { n_vars, generator }
+    }
+}
+
+impl<F: PrimeField, G: Fn(usize) -> F> EvalSource<F> for FnSource<G> {
+    fn n_vars(&self) -> usize {
+        self.n_vars
+    }
+
+    fn visit_evals(&self, visit: &mut dyn FnMut(F)) -> io::Result<()> {
+        (0..1usize << self.n_vars).for_each(|i| visit((self.generator)(i)));
+        Ok(())
+    }
+}
+
+/// One pass computing the sums of round i + 1, given eq(r, ·) over the first i variables
+fn streamed_round_sums<F: PrimeField, S: EvalSource<F> + ?Sized>(
+    source: &S,
+    eq: &MLPoly<F>,
+) -> io::Result<(F, F)> {
+    let low_bits = eq.n_vars;
+    let low_mask = (1 << low_bits) - 1;
+    let mut sums = [F::ZERO; 2];
+    let mut index = 0usize;
+    source.visit_evals(&mut |e| {
+        let bit = (index >> low_bits) & 1;
+        sums[bit] += eq.evals[index & low_mask] * e;
+        index += 1;
+    })?;
+    Ok((sums[0], sums[1]))
+}
+
+/// One pass computing f(r_1, ..., r_k, ·) from eq(r, ·) over the first k variables
+fn materialize<F: PrimeField, S: EvalSource<F> + ?Sized>(
+    source: &S,
+    eq: &MLPoly<F>,
+) -> io::Result<MLPoly<F>> {
+    let low_bits = eq.n_vars;
+    let low_mask = (1 << low_bits) - 1;
+    let mut evals = vec![F::ZERO; 1 << (source.n_vars() - low_bits)];
+    let mut index = 0usize;
+    source.visit_evals(&mut |e| {
+        evals[index >> low_bits] += eq.evals[index & low_mask] * e;
+        index += 1;
+    })?;
+    Ok(MLPoly::from_evals(evals))
+}
+
+/// Generate a sumcheck proof from a stream of evaluations in O(2^{n/2}) space
+///
+/// # Arguments
+/// * `stmt` - Public statement containing n_vars and claimed sum
+/// * `source` - The evaluations of f, visited ⌊n/2⌋ + 1 times
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The same proof, challenge point and final evaluation as `prove_with_output`
+/// with the default configuration, or the error of the first failed read
+///
+/// # Panics
+/// Panics if `source` does not have `stmt.n_vars` variables
+pub fn prove_streaming<F: PrimeField, S: EvalSource<F> + ?Sized>(
+    stmt: &Statement<F>,
+    source: &S,
+    transcript: &mut Transcript,
+) -> io::Result<ProverOutput<F>> {
+    let n_vars = stmt.n_vars;
+    assert_eq!(
+        source.n_vars(),
+        n_vars,
+        "source has wrong number of variables"
+    );
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+
+    let mut round_polys = Vec::with_capacity(n_vars);
+    let mut point = Vec::with_capacity(n_vars);
+
+    // Streamed rounds: the eq table doubles each round and stays below 2^{n/2}
+    let mut eq = MLPoly::eq_table(&[]);
+    for _ in 0..n_vars / 2 {
+        let (g0, g1) = streamed_round_sums(source, &eq)?;
+        let round_poly = RoundPoly::new(g0, g1);
+        append_round_poly(&round_poly, transcript);
+        round_polys.push(round_poly);
+
+        let r: F = transcript.challenge_scalar(b"r");
+        point.push(r);
+        eq = MLPoly::eq_table(&point);
+    }
+
+    // The rest fits in memory
+    let mut poly = materialize(source, &eq)?;
+    drop(eq);
+    run_rounds(
+        &mut poly,
+        BindingOrder::LowToHigh,
+        transcript,
+        &mut round_polys,
+        &mut point,
+    );
+
+    Ok(ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![poly.evals[0]],
+    })
+}
+
+/// Generate a sumcheck proof for a memory-mapped polynomial
+///
+/// The mapping is read twice: once for the round-1 sums, and once to bind x_1,
+/// which loads f(r_1, ·), half the size of the file, into memory and yields the
+/// round-2 sums in the same pass. The remaining rounds fold it in place. Use
+/// `prove_streaming` when even half does not fit.
+///
+/// # Returns
+/// The same proof, challenge point and final evaluation as `prove_with_output`
+/// with the default configuration, or `InvalidData` if an element is corrupt
+///
+/// # Panics
+/// Panics if `poly` does not have `stmt.n_vars` variables
+pub fn prove_mapped<F: PrimeField>(
+    stmt: &Statement<F>,
+    poly: &MmapMLPoly<F>,
+    transcript: &mut Transcript,
+) -> io::Result<ProverOutput<F>> {
+    let n_vars = stmt.n_vars;
+    assert_eq!(
+        poly.n_vars, n_vars,
+        "polynomial has wrong number of variables"
+    );
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+
+    let mut round_polys = Vec::with_capacity(n_vars);
+    let mut point = Vec::with_capacity(n_vars);
+    if n_vars == 0 {
+        return Ok(ProverOutput {
+            proof: SumcheckProof { round_polys },
+            point,
+            final_evals: poly.iter().collect::<io::Result<_>>()?,
+        });
+    }
+
+    let (g0, g1) = poly.round_sum_g0_g1()?;
+    let round_poly = RoundPoly::new(g0, g1);
+    append_round_poly(&round_poly, transcript);
+    round_polys.push(round_poly);
+
+    let r: F = transcript.challenge_scalar(b"r");
+    point.push(r);
+    let (mut folded, sums) = if n_vars >= 2 {
+        poly.fold_and_round_sum(r)?
+    } else {
+        (poly.fold_first_var(r)?, (F::ZERO, F::ZERO))
+    };
+    run_rounds_from(
+        &mut folded,
+        sums,
+        BindingOrder::LowToHigh,
+        transcript,
+        &mut round_polys,
+        &mut point,
+    );
+
+    Ok(ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: vec![folded.evals[0]],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use crate::protocol::{prove_with_output, verify};
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+    use std::cell::Cell;
+
+    fn assert_same_output(a: &ProverOutput<Fr>, b: &ProverOutput<Fr>) {
+        assert_eq!(a.proof.num_rounds(), b.proof.num_rounds());
+        for (x, y) in a.proof.round_polys.iter().zip(&b.proof.round_polys) {
+            assert_eq!(x.evals, y.evals);
+        }
+        assert_eq!(a.point, b.point);
+        assert_eq!(a.final_evals, b.final_evals);
+    }
+
+    #[test]
+    fn test_streaming_matches_prove() {
+        let mut rng = ark_std::test_rng();
+        for n_vars in [0, 1, 2, 5, 8] {
+            let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+            let poly = MLPoly { n_vars, evals };
+            let stmt = Statement {
+                n_vars,
+                claim_sum: poly.sum_all(),
+            };
+
+            let mut transcript = Transcript::new(b"streaming-test");
+            let streamed = prove_streaming(&stmt, &poly, &mut transcript).unwrap();
+
+            let mut transcript = Transcript::new(b"streaming-test");
+            let config = SumcheckConfig::default();
+            let dense = prove_with_output(&stmt, &poly, &mut transcript, &config);
+
+            assert_same_output(&streamed, &dense);
+        }
+    }
+
+    #[test]
+    fn test_generator_source_verifies() {
+        // f(i) = i^2 + 7, never stored as a whole
+        let n_vars = 9;
+        let source = FnSource::new(n_vars, |i: usize| Fr::from((i * i + 7) as u64));
+        let poly = MLPoly::from_evals((0..1 << n_vars).map(&source.generator).collect());
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+
+        let mut transcript = Transcript::new(b"streaming-test");
+        let out = prove_streaming(&stmt, &source, &mut transcript).unwrap();
+
+        let oracle = PolyOracle::new(poly);
+        let mut transcript = Transcript::new(b"streaming-test");
+        let result = verify(&stmt, &out.proof, &oracle, &mut transcript);
+        assert!(result.unwrap(), "streamed proof should verify");
+    }
+
+    #[test]
+    fn test_mapped_file_matches_prove() {
+        let mut rng = ark_std::test_rng();
+        let path = std::env::temp_dir().join(format!("sumcheck-mapped-{}.bin", std::process::id()));
+        for n_vars in [0, 1, 7] {
+            let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+            let poly = MLPoly { n_vars, evals };
+            let stmt = Statement {
+                n_vars,
+                claim_sum: poly.sum_all(),
+            };
+            poly.write_to_file(&path).unwrap();
+            let view = MmapMLPoly::<Fr>::open(&path).unwrap();
+
+            let mut transcript = Transcript::new(b"streaming-test");
+            let config = SumcheckConfig::default();
+            let dense = prove_with_output(&stmt, &poly, &mut transcript, &config);
+
+            let mut transcript = Transcript::new(b"streaming-test");
+            assert_same_output(
+                &prove_mapped(&stmt, &view, &mut transcript).unwrap(),
+                &dense,
+            );
+            let mut transcript = Transcript::new(b"streaming-test");
+            assert_same_output(
+                &prove_streaming(&stmt, &view, &mut transcript).unwrap(),
+                &dense,
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mapped_corrupt_element_is_an_error() {
+        let path =
+            std::env::temp_dir().join(format!("sumcheck-corrupt-{}.bin", std::process::id()));
+        let n_vars = 4;
+        let poly = MLPoly::from_evals((0..1u64 << n_vars).map(Fr::from).collect());
+        poly.write_to_file(&path).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        let len = bytes.len();
+        bytes[len - 32..].fill(0xff);
+        std::fs::write(&path, &bytes).unwrap();
+
+        let view = MmapMLPoly::<Fr>::open(&path).unwrap();
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let mut transcript = Transcript::new(b"streaming-test");
+        assert!(prove_mapped(&stmt, &view, &mut transcript).is_err());
+        let mut transcript = Transcript::new(b"streaming-test");
+        assert!(prove_streaming(&stmt, &view, &mut transcript).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_number_of_passes() {
+        let n_vars = 10;
+        let calls = Cell::new(0usize);
+        let source = FnSource::new(n_vars, |i: usize| {
+            calls.set(calls.get() + 1);
+            Fr::from(i as u64)
+        });
+        let stmt = Statement {
+            n_vars,
+            claim_sum: Fr::from(((1u64 << n_vars) - 1) << (n_vars - 1)),
+        };
+
+        let mut transcript = Transcript::new(b"streaming-test");
+        prove_streaming(&stmt, &source, &mut transcript).unwrap();
+        assert_eq!(calls.get(), (n_vars / 2 + 1) << n_vars);
+    }
+}
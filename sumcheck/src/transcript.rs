@@ -1,4 +1,5 @@
-use ark_ff::PrimeField;
+use ark_ff::{Field, PrimeField};
+use ark_serialize::CanonicalSerialize;
 use blake2::Blake2s256;
 use digest::{Digest, FixedOutputReset};
 
@@ -25,24 +26,57 @@ impl Transcript {
         self.h.update(bytes);
     }
 
-    pub fn append_field<F:PrimeField>(&mut self, label: &'static [u8], x: &F) {
+    pub fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.append_message(label, &x.to_le_bytes());
+    }
+
+    pub fn append_field<F:Field>(&mut self, label: &'static [u8], x: &F) {
+        self.append_serializable(label, x);
+    }
+
+    /// Append any serializable value, e.g. a polynomial commitment
+    pub fn append_serializable<T: CanonicalSerialize + ?Sized>(&mut self, label: &'static [u8], x: &T) {
         let mut buf = Vec::new();
         x.serialize_compressed(&mut buf).expect("serialize");
         self.append_message(label, &buf);
     }
 
-    pub fn challenge_scalar<F:PrimeField>(&mut self, label: &'static [u8]) -> F {
+    /// Append a list of commitments, in order, under one label each
+    pub fn append_commitments<C: CanonicalSerialize>(&mut self, commitments: &[C]) {
+        for c in commitments {
+            self.append_serializable(b"commitment", c);
+        }
+    }
+
+    /// Draw a challenge in F
+    ///
+    /// Prime fields take the 32-byte hash output reduced mod p. For an extension
+    /// of degree d each of the d coordinates over the base prime field gets its
+    /// own output: the first is the one a prime field would use, the others are
+    /// derived from it and the coordinate index.
+    pub fn challenge_scalar<F:Field>(&mut self, label: &'static [u8]) -> F {
         let mut fork = self.h.clone();
         fork.update(b"chal");
         fork.update((label.len() as u64).to_le_bytes());
         fork.update(label);
-        fork.update((self.ctr as u64).to_le_bytes());
+        fork.update(self.ctr.to_le_bytes());
 
         let out = fork.finalize_fixed_reset();
 
         self.h.update(b"ratchet");
         self.h.update(out.as_slice());
         self.ctr += 1;
-        F::from_le_bytes_mod_order(&out)
+
+        let degree = F::extension_degree();
+        let mut coords = Vec::with_capacity(degree as usize);
+        coords.push(F::BasePrimeField::from_le_bytes_mod_order(&out));
+        for i in 1..degree {
+            let mut coord = Blake2s256::new();
+            coord.update(b"ext");
+            coord.update(out.as_slice());
+            coord.update(i.to_le_bytes());
+            coords.push(F::BasePrimeField::from_le_bytes_mod_order(&coord.finalize()));
+        }
+        F::from_base_prime_field_elems(&coords).expect("one coordinate per extension degree")
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,252 @@
+//! GKR for binary trees of layered relations
+//!
+//! A tree starts from k leaf polynomials in n variables. Level i has k
+//! polynomials in i variables, each obtained from the two halves of the level
+//! below: writing c(b, x) for a child with its first variable split off, parent j
+//! is R_j(c_1(0, x), c_1(1, x), ..., c_k(0, x), c_k(1, x)) for a fixed low-degree
+//! rule R_j. Products (v = v_0 · v_1) and fraction sums (p = p_0 q_1 + p_1 q_0,
+//! q = q_0 q_1) are the two rules in use.
+//!
+//! The roots are sent in the clear. Claims on level i at a point r are batched
+//! with powers of a random λ and reduced by a sumcheck of
+//! ∑_x eq(r, x) · ∑_j λ^j R_j(...) to claims on both halves of the children at a
+//! point ρ; a random μ merges each pair of halves into one claim at (μ, ρ). After
+//! n levels only claims on the leaves remain.
+
+use ark_ff::PrimeField;
+use mlpoly::{eq_eval, MLPoly};
+
+use crate::error::{Error, Result};
+use crate::transcript::Transcript;
+use crate::types::{Statement, TreeLayerProof, TreeProof};
+use crate::virtual_poly::{prove_virtual, verify_virtual_rounds, Composition, VirtualPoly};
+
+/// eq · ∑_j λ^j R_j, with eq at index 0 and child half (i, b) at index 1 + 2i + b
+fn layer_composition<F: PrimeField>(rule: &[Composition<F>], lambda: F) -> Composition<F> {
+    let mut terms = Vec::new();
+    let mut power = F::ONE;
+    for parent in rule {
+        for (c, idx) in &parent.terms {
+            let mut shifted = Vec::with_capacity(idx.len() + 1);
+            shifted.push(0);
+            shifted.extend(idx.iter().map(|&i| i + 1));
+            terms.push((power * c, shifted));
+        }
+        power *= lambda;
+    }
+    Composition {
+        num_mles: 1 + 2 * rule.len(),
+        terms,
+    }
+}
+
+/// ∑_j λ^j claims_j
+fn batch<F: PrimeField>(claims: &[F], lambda: F) -> F {
+    claims
+        .iter()
+        .rev()
+        .fold(F::ZERO, |acc, &c| acc * lambda + c)
+}
+
+/// The halves c(0, ·) and c(1, ·) of every polynomial, in rule index order
+fn halves<F: PrimeField>(polys: &[MLPoly<F>]) -> Vec<MLPoly<F>> {
+    polys
+        .iter()
+        .flat_map(|p| [p.fix_var(0, false), p.fix_var(0, true)])
+        .collect()
+}
+
+/// Prove the roots of the tree grown from `leaves` under `rule`
+///
+/// # Returns
+/// The proof, and the point and values of the final claims on the leaves
+///
+/// # Panics
+/// Panics if the leaves do not all have the same number of variables, or if
+/// their number differs from the number of parent rules
+pub(crate) fn prove_tree<F: PrimeField>(
+    rule: &[Composition<F>],
+    leaves: Vec<MLPoly<F>>,
+    transcript: &mut Transcript,
+) -> (TreeProof<F>, Vec<F>, Vec<F>) {
+    assert_eq!(leaves.len(), rule.len(), "one leaf polynomial per rule");
+    let n_vars = leaves[0].n_vars;
+    assert!(
+        leaves.iter().all(|p| p.n_vars == n_vars),
+        "leaf polynomials have different numbers of variables"
+    );
+
+    // levels[i] holds the polynomials of level i; levels[n_vars] are the leaves
+    let mut levels = vec![leaves];
+    for _ in 0..n_vars {
+        let children = halves(levels.last().unwrap());
+        let size = children[0].len();
+        let mut vals = vec![F::ZERO; children.len()];
+        let parents = rule
+            .iter()
+            .map(|r| {
+                let evals = (0..size)
+                    .map(|x| {
+                        for (v, c) in vals.iter_mut().zip(&children) {
+                            *v = c.evals[x];
+                        }
+                        r.evaluate(&vals)
+                    })
+                    .collect();
+                MLPoly::from_evals(evals)
+            })
+            .collect();
+        levels.push(parents);
+    }
+    levels.reverse();
+
+    let roots: Vec<F> = levels[0].iter().map(|p| p.evals[0]).collect();
+    for v in &roots {
+        transcript.append_field(b"root", v);
+    }
+
+    let mut point = Vec::with_capacity(n_vars);
+    let mut claims = roots.clone();
+    let mut layers = Vec::with_capacity(n_vars);
+    for (i, children) in levels.iter().enumerate().skip(1) {
+        let lambda: F = transcript.challenge_scalar(b"lambda");
+        let eq = MLPoly::eq_table(&point);
+        let halves = halves(children);
+        let mut mles = vec![&eq];
+        mles.extend(&halves);
+        let poly = VirtualPoly {
+            n_vars: i - 1,
+            composition: layer_composition(rule, lambda),
+            mles,
+        };
+        let stmt = Statement {
+            n_vars: i - 1,
+            claim_sum: batch(&claims, lambda),
+        };
+        let out = prove_virtual(&stmt, &poly, transcript);
+
+        let child_evals = out.final_evals[1..].to_vec();
+        for v in &child_evals {
+            transcript.append_field(b"child_eval", v);
+        }
+        let mu: F = transcript.challenge_scalar(b"mu");
+        point = std::iter::once(mu).chain(out.point).collect();
+        claims = child_evals
+            .chunks(2)
+            .map(|pair| pair[0] + mu * (pair[1] - pair[0]))
+            .collect();
+        layers.push(TreeLayerProof {
+            sumcheck: out.proof,
+            child_evals,
+        });
+    }
+
+    (TreeProof { roots, layers }, point, claims)
+}
+
+/// Check a tree proof and return the point and claimed values of the leaves
+///
+/// The roots in `proof.roots` are accepted as given; the caller checks them.
+///
+/// # Returns
+/// * `Ok((point, claims))` if every layer checks out
+/// * `Err(_)` if the shapes disagree, a round check fails or a layer check fails
+pub(crate) fn verify_tree<F: PrimeField>(
+    rule: &[Composition<F>],
+    n_vars: usize,
+    proof: &TreeProof<F>,
+    transcript: &mut Transcript,
+) -> Result<(Vec<F>, Vec<F>)> {
+    if proof.roots.len() != rule.len() {
+        return Err(Error::DimensionMismatch("wrong number of tree roots"));
+    }
+    if proof.layers.len() != n_vars {
+        return Err(Error::DimensionMismatch("wrong number of tree layers"));
+    }
+    for v in &proof.roots {
+        transcript.append_field(b"root", v);
+    }
+
+    let mut point: Vec<F> = Vec::with_capacity(n_vars);
+    let mut claims = proof.roots.clone();
+    for (i, layer) in proof.layers.iter().enumerate() {
+        if layer.child_evals.len() != 2 * rule.len() {
+            return Err(Error::DimensionMismatch(
+                "wrong number of child evaluations",
+            ));
+        }
+        let lambda: F = transcript.challenge_scalar(b"lambda");
+        let composition = layer_composition(rule, lambda);
+        let stmt = Statement {
+            n_vars: i,
+            claim_sum: batch(&claims, lambda),
+        };
+        let subclaim = verify_virtual_rounds(&stmt, &composition, &layer.sumcheck, transcript)?;
+
+        let mut vals = Vec::with_capacity(composition.num_mles);
+        vals.push(eq_eval(&point, &subclaim.point));
+        vals.extend(&layer.child_evals);
+        if composition.evaluate(&vals) != subclaim.expected_eval {
+            return Err(Error::InvalidProof("tree layer check failed"));
+        }
+
+        for v in &layer.child_evals {
+            transcript.append_field(b"child_eval", v);
+        }
+        let mu: F = transcript.challenge_scalar(b"mu");
+        point = std::iter::once(mu).chain(subclaim.point).collect();
+        claims = layer
+            .child_evals
+            .chunks(2)
+            .map(|pair| pair[0] + mu * (pair[1] - pair[0]))
+            .collect();
+    }
+
+    Ok((point, claims))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    /// v = v_0 · v_1
+    fn product_rule() -> Vec<Composition<Fr>> {
+        vec![Composition {
+            num_mles: 2,
+            terms: vec![(Fr::from(1u64), vec![0, 1])],
+        }]
+    }
+
+    #[test]
+    fn test_tree_roundtrip() {
+        let mut rng = ark_std::test_rng();
+        for n_vars in [0, 1, 5] {
+            let leaf = MLPoly::from_evals((0..1 << n_vars).map(|_| Fr::rand(&mut rng)).collect());
+            let rule = product_rule();
+
+            let mut transcript = Transcript::new(b"tree-test");
+            let (proof, point, claims) = prove_tree(&rule, vec![leaf.clone()], &mut transcript);
+            assert_eq!(proof.roots, vec![leaf.evals.iter().product::<Fr>()]);
+            assert_eq!(claims, vec![leaf.eval_at(&point)]);
+
+            let mut transcript = Transcript::new(b"tree-test");
+            let (v_point, v_claims) = verify_tree(&rule, n_vars, &proof, &mut transcript).unwrap();
+            assert_eq!((v_point, v_claims), (point, claims));
+        }
+    }
+
+    #[test]
+    fn test_tree_tampered_root_fails() {
+        let mut rng = ark_std::test_rng();
+        let leaf = MLPoly::from_evals((0..16).map(|_| Fr::rand(&mut rng)).collect());
+        let rule = product_rule();
+        let mut transcript = Transcript::new(b"tree-test");
+        let (mut proof, _, _) = prove_tree(&rule, vec![leaf], &mut transcript);
+        proof.roots[0] += Fr::from(1u64);
+
+        let mut transcript = Transcript::new(b"tree-test");
+        assert!(verify_tree(&rule, 4, &proof, &mut transcript).is_err());
+    }
+}
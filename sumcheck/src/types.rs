@@ -12,19 +12,102 @@ pub struct Statement<F: Field> {
     pub claim_sum: F,
 }
 
-/// A degree-1 polynomial represented by its evaluations at 0 and 1
+/// Versioned domain label absorbed ahead of the statement when binding is enabled
+pub const STATEMENT_DOMAIN: &[u8] = b"sumcheck-statement-v1";
+
+/// Order in which the sumcheck rounds bind the variables
+///
+/// Evaluations are indexed LSB-first, so `LowToHigh` binds the least-significant
+/// index bit x_1 first and `HighToLow` binds x_n first. Either way the final
+/// point is reported in the polynomial's own variable order (x_1, ..., x_n).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BindingOrder {
+    /// Round i binds x_i
+    #[default]
+    LowToHigh,
+    /// Round i binds x_{n+1-i}
+    HighToLow,
+}
+
+/// Options controlling what prover and verifier absorb before round 1,
+/// and in which order the rounds bind the variables
+#[derive(Clone, Debug)]
+pub struct SumcheckConfig {
+    /// Absorb the statement (n_vars, claim_sum) and `public_input` into the transcript
+    pub bind_statement: bool,
+    /// Caller-supplied public input (e.g. a commitment to the polynomial), bound with the statement
+    pub public_input: Vec<u8>,
+    /// Variable binding order; prover and verifier must agree on it
+    pub binding_order: BindingOrder,
+}
+
+impl Default for SumcheckConfig {
+    fn default() -> Self {
+        Self {
+            bind_statement: true,
+            public_input: Vec::new(),
+            binding_order: BindingOrder::LowToHigh,
+        }
+    }
+}
+
+impl SumcheckConfig {
+    /// Bind the statement together with the given public input
+    pub fn with_public_input(public_input: Vec<u8>) -> Self {
+        Self {
+            bind_statement: true,
+            public_input,
+            binding_order: BindingOrder::LowToHigh,
+        }
+    }
+
+    /// Legacy mode: only round polynomials are absorbed
+    ///
+    /// Challenges do not depend on the claim, so this is only sound if the caller
+    /// has already bound the statement into the transcript.
+    pub fn unbound() -> Self {
+        Self {
+            bind_statement: false,
+            public_input: Vec::new(),
+            binding_order: BindingOrder::LowToHigh,
+        }
+    }
+
+    /// Use the given variable binding order
+    pub fn with_binding_order(mut self, binding_order: BindingOrder) -> Self {
+        self.binding_order = binding_order;
+        self
+    }
+}
+
+/// A univariate round polynomial of degree d represented by its evaluations at 0, 1, ..., d
 #[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
 pub struct RoundPoly<F: Field> {
-    /// [g(0), g(1)] - evaluations at 0 and 1
+    /// [g(0), g(1), ..., g(d)] - evaluations at 0, 1, ..., d
     pub evals: Vec<F>,
 }
 
 impl<F: Field> RoundPoly<F> {
-    /// Create a new round polynomial from evaluations at 0 and 1
+    /// Create a new degree-1 round polynomial from evaluations at 0 and 1
     pub fn new(g0: F, g1: F) -> Self {
         Self { evals: vec![g0, g1] }
     }
 
+    /// Create a round polynomial from evaluations at 0, 1, ..., d
+    ///
+    /// # Panics
+    /// Panics if fewer than two evaluations are given
+    pub fn from_evals(evals: Vec<F>) -> Self {
+        assert!(evals.len() >= 2, "round polynomial needs at least g(0) and g(1)");
+        Self { evals }
+    }
+
+    /// Degree bound d (number of evaluations minus one)
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.evals.len() - 1
+    }
+
     /// Get g(0)
     #[inline]
     pub fn eval_0(&self) -> F {
@@ -37,16 +120,74 @@ impl<F: Field> RoundPoly<F> {
         self.evals[1]
     }
 
-    /// Return coefficients [c0, c1] where g(x) = c0 + c1 * x
-    pub fn coeffs(&self) -> (F, F) {
-        let c0 = self.evals[0];
-        let c1 = self.evals[1] - self.evals[0];
-        (c0, c1)
+    /// Return coefficients [c_0, ..., c_d] where g(x) = ∑_i c_i * x^i
+    ///
+    /// Builds the Newton form over the nodes 0, 1, ..., d from divided
+    /// differences and expands it into the monomial basis.
+    pub fn coeffs(&self) -> Vec<F> {
+        let d = self.degree();
+
+        // Divided differences: nodes i and i - j are j apart
+        let mut dd = self.evals.clone();
+        for j in 1..=d {
+            let inv = F::from(j as u64).inverse().expect("j is nonzero");
+            for i in (j..=d).rev() {
+                dd[i] = (dd[i] - dd[i - 1]) * inv;
+            }
+        }
+
+        // Horner on the Newton form: c <- c * (x - k) + dd[k]
+        let mut coeffs = vec![F::ZERO; d + 1];
+        coeffs[0] = dd[d];
+        for k in (0..d).rev() {
+            let node = F::from(k as u64);
+            for i in (1..=d).rev() {
+                coeffs[i] = coeffs[i - 1] - node * coeffs[i];
+            }
+            coeffs[0] = dd[k] - node * coeffs[0];
+        }
+        coeffs
     }
 
-    /// Evaluate at point x: g(x) = g(0) + (g(1) - g(0)) * x
+    /// Evaluate at point x by Lagrange interpolation over the nodes 0, 1, ..., d
+    ///
+    /// Degree 1 uses g(x) = g(0) + (g(1) - g(0)) * x; higher degrees use the
+    /// barycentric formula g(x) = l(x) * ∑_i w_i * g(i) / (x - i).
     pub fn eval(&self, x: F) -> F {
-        self.evals[0] + (self.evals[1] - self.evals[0]) * x
+        let d = self.degree();
+        if d == 1 {
+            return self.evals[0] + (self.evals[1] - self.evals[0]) * x;
+        }
+
+        // x - i for every node; hitting a node directly returns its evaluation
+        let mut diffs: Vec<F> = Vec::with_capacity(d + 1);
+        let mut node = F::ZERO;
+        for &e in &self.evals {
+            let diff = x - node;
+            if diff.is_zero() {
+                return e;
+            }
+            diffs.push(diff);
+            node += F::ONE;
+        }
+        let l: F = diffs.iter().product();
+
+        // w_i^{-1} = ∏_{j != i} (i - j) = (-1)^{d-i} * i! * (d-i)!
+        let mut fact = vec![F::ONE; d + 1];
+        for i in 1..=d {
+            fact[i] = fact[i - 1] * F::from(i as u64);
+        }
+        let mut denoms: Vec<F> = (0..=d)
+            .map(|i| {
+                let w_inv = fact[i] * fact[d - i];
+                let w_inv = if (d - i) % 2 == 1 { -w_inv } else { w_inv };
+                w_inv * diffs[i]
+            })
+            .collect();
+        ark_ff::batch_inversion(&mut denoms);
+
+        let sum: F = self.evals.iter().zip(&denoms).map(|(&e, &inv)| e * inv).sum();
+        l * sum
     }
 }
 
@@ -64,3 +205,245 @@ impl<F: Field> SumcheckProof<F> {
     }
 }
 
+/// Proof for a batch of sumcheck claims with possibly different n_vars
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BatchedProof<F: Field> {
+    /// Single sumcheck over max_i n_i variables
+    pub sumcheck: SumcheckProof<F>,
+    /// f_i(r_1, ..., r_{n_i}) for every instance
+    pub final_evals: Vec<F>,
+}
+
+/// GKR proof for one gate layer, reducing a claim on its values to one on its inputs
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GkrLayerProof<F: Field> {
+    /// Sumcheck over the left input x (degree 2)
+    pub phase1: SumcheckProof<F>,
+    /// Sumcheck over the right input y with x bound (degree 2)
+    pub phase2: SumcheckProof<F>,
+    /// q(t) = V(ℓ(t)) on the line ℓ through x and y, at t = 0, ..., max(s, 1);
+    /// q(0) and q(1) are the claimed V(x) and V(y)
+    pub line: RoundPoly<F>,
+}
+
+/// GKR proof for a layered circuit: one layer proof per gate layer, output layer first
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GkrProof<F: Field> {
+    pub layers: Vec<GkrLayerProof<F>>,
+}
+
+/// Prover-side result of a GKR run
+#[derive(Clone, Debug)]
+pub struct GkrProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: GkrProof<F>,
+    /// Values of the output gates
+    pub outputs: Vec<F>,
+    /// Point r at which the verifier queries the input MLE
+    pub input_point: Vec<F>,
+    /// Value of the input MLE at `input_point`
+    pub input_eval: F,
+}
+
+/// Spartan proof that an R1CS instance is satisfied
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SpartanProof<F: Field> {
+    /// Zerocheck of Az ∘ Bz − Cz over the constraints (degree 3)
+    pub outer: SumcheckProof<F>,
+    /// Claimed (Az)(r_x), (Bz)(r_x) and (Cz)(r_x)
+    pub a_eval: F,
+    pub b_eval: F,
+    pub c_eval: F,
+    /// Sumcheck of the combined matrix row at r_x times z (degree 2)
+    pub inner: SumcheckProof<F>,
+}
+
+/// Prover-side result of a Spartan run
+#[derive(Clone, Debug)]
+pub struct SpartanProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: SpartanProof<F>,
+    /// Point at which the verifier queries the witness MLE
+    pub witness_point: Vec<F>,
+    /// Value of the witness MLE at `witness_point`
+    pub witness_eval: F,
+}
+
+/// One layer of a binary-tree GKR, reducing claims on a level to claims on the level below
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct TreeLayerProof<F: Field> {
+    /// Sumcheck of the eq-weighted layer relation (degree 3)
+    pub sumcheck: SumcheckProof<F>,
+    /// Claimed values of both halves of every child polynomial at the sumcheck point
+    pub child_evals: Vec<F>,
+}
+
+/// Binary-tree GKR proof: the root values and one layer proof per level, root first
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct TreeProof<F: Field> {
+    pub roots: Vec<F>,
+    pub layers: Vec<TreeLayerProof<F>>,
+}
+
+/// LogUp proof: fractional-sum trees over the witness and over the table
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct LogUpProof<F: Field> {
+    pub witness: TreeProof<F>,
+    pub table: TreeProof<F>,
+}
+
+/// Evaluation claims a LogUp verifier is left with, to be checked against oracles
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogUpClaims<F: Field> {
+    /// Point and claimed value of the witness MLE
+    pub witness_point: Vec<F>,
+    pub witness_eval: F,
+    /// Point shared by the table and multiplicity claims
+    pub table_point: Vec<F>,
+    pub table_eval: F,
+    pub multiplicity_eval: F,
+}
+
+/// Prover-side result of a LogUp run
+#[derive(Clone, Debug)]
+pub struct LogUpProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: LogUpProof<F>,
+    /// The claims the verifier will derive, for opening commitments
+    pub claims: LogUpClaims<F>,
+}
+
+/// Grand product proof: a product tree over each polynomial
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GrandProductProof<F: Field> {
+    pub tree: TreeProof<F>,
+}
+
+impl<F: Field> GrandProductProof<F> {
+    /// Products claimed by the proof, one per polynomial
+    pub fn products(&self) -> &[F] {
+        &self.tree.roots
+    }
+}
+
+/// Evaluation claims a grand product verifier is left with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrandProductClaims<F: Field> {
+    /// Point shared by all claims
+    pub point: Vec<F>,
+    /// Claimed value of each polynomial at `point`
+    pub evals: Vec<F>,
+}
+
+/// Prover-side result of a grand product run
+#[derive(Clone, Debug)]
+pub struct GrandProductProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: GrandProductProof<F>,
+    /// The claims the verifier will derive, for opening commitments
+    pub claims: GrandProductClaims<F>,
+}
+
+/// Permutation proof: a grand product over both sides, plus the claimed σ(r)
+#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PermutationProof<F: Field> {
+    pub products: GrandProductProof<F>,
+    /// Claimed value of the permutation's index MLE at the final point
+    pub sigma_eval: F,
+}
+
+/// Evaluation claims a permutation verifier is left with, to be checked against oracles
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PermutationClaims<F: Field> {
+    /// Point shared by all claims
+    pub point: Vec<F>,
+    pub f_eval: F,
+    pub g_eval: F,
+    pub sigma_eval: F,
+}
+
+/// Prover-side result of a permutation check
+#[derive(Clone, Debug)]
+pub struct PermutationProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: PermutationProof<F>,
+    /// The claims the verifier will derive, for opening commitments
+    pub claims: PermutationClaims<F>,
+}
+
+/// Evaluation claims a multiset-equality verifier is left with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultisetClaims<F: Field> {
+    /// Point shared by both claims
+    pub point: Vec<F>,
+    pub f_eval: F,
+    pub g_eval: F,
+}
+
+/// Prover-side result of a multiset-equality check
+#[derive(Clone, Debug)]
+pub struct MultisetProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: GrandProductProof<F>,
+    /// The claims the verifier will derive, for opening commitments
+    pub claims: MultisetClaims<F>,
+}
+
+/// What the verifier is left with after the round checks: the final claim
+/// `expected_eval` must equal the summed polynomial evaluated at `point`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubClaim<F: Field> {
+    /// Challenges in the order the rounds drew them (binding order)
+    ///
+    /// With `BindingOrder::LowToHigh` this is already variable order x_1, ..., x_n.
+    /// After a `HighToLow` proof the first challenge binds x_n, so reverse the
+    /// point before evaluating the polynomial at it, as `verify_with_config`
+    /// does; `ProverOutput::point` is always in variable order.
+    pub point: Vec<F>,
+    /// Value the polynomial must take at `point`
+    pub expected_eval: F,
+}
+
+/// Prover-side result of a sumcheck run
+#[derive(Clone, Debug)]
+pub struct ProverOutput<F: Field> {
+    /// The proof sent to the verifier
+    pub proof: SumcheckProof<F>,
+    /// Challenges for x_1, ..., x_n, in variable order whatever the binding order
+    pub point: Vec<F>,
+    /// Evaluation of each input polynomial at `point`
+    pub final_evals: Vec<F>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    #[test]
+    fn test_round_poly_interpolation() {
+        // g(x) = 3 + 2x + 5x^2 + x^3
+        let g = |x: Fr| Fr::from(3u64) + Fr::from(2u64) * x + Fr::from(5u64) * x * x + x * x * x;
+        let evals: Vec<Fr> = (0..4u64).map(|i| g(Fr::from(i))).collect();
+        let round_poly = RoundPoly::from_evals(evals);
+        assert_eq!(round_poly.degree(), 3);
+        let expected: Vec<Fr> = [3u64, 2, 5, 1].iter().map(|&c| Fr::from(c)).collect();
+        assert_eq!(round_poly.coeffs(), expected);
+
+        let mut rng = ark_std::test_rng();
+        for _ in 0..4 {
+            let x = Fr::rand(&mut rng);
+            assert_eq!(round_poly.eval(x), g(x));
+        }
+        // Nodes themselves
+        assert_eq!(round_poly.eval(Fr::from(2u64)), g(Fr::from(2u64)));
+    }
+
+    #[test]
+    fn test_round_poly_linear() {
+        let round_poly = RoundPoly::new(Fr::from(4u64), Fr::from(7u64));
+        assert_eq!(round_poly.coeffs(), vec![Fr::from(4u64), Fr::from(3u64)]);
+        assert_eq!(round_poly.eval(Fr::from(2u64)), Fr::from(10u64));
+    }
+}
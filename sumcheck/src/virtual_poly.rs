@@ -0,0 +1,583 @@
+//! Sumcheck for an arbitrary low-degree composition of multilinear polynomials
+//!
+//! A virtual polynomial is G(f_1(x), ..., f_k(x)) = ∑_j c_j · ∏_{i ∈ S_j} f_i(x),
+//! a sum of weighted products over a shared list of `MLPoly` references.
+//! Its round polynomials have degree max_j |S_j|.
+
+use ark_ff::PrimeField;
+use mlpoly::MLPoly;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::protocol::{absorb_statement, append_round_poly, verify_rounds};
+use crate::transcript::Transcript;
+use crate::types::{ProverOutput, RoundPoly, Statement, SubClaim, SumcheckConfig, SumcheckProof};
+
+/// Public shape of a virtual polynomial: the composition function G
+///
+/// Each term `(c, indices)` contributes c · ∏_{i ∈ indices} f_i.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Composition<F: PrimeField> {
+    /// Number of distinct multilinear polynomials G is applied to
+    pub num_mles: usize,
+    /// Weighted products, as (coefficient, indices into the polynomial list)
+    pub terms: Vec<(F, Vec<usize>)>,
+}
+
+impl<F: PrimeField> Composition<F> {
+    /// Degree of G, i.e. the length of its longest product
+    pub fn degree(&self) -> usize {
+        self.terms
+            .iter()
+            .map(|(_, idx)| idx.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Evaluate G given the value of every f_i
+    pub fn evaluate(&self, mle_evals: &[F]) -> F {
+        assert_eq!(
+            mle_evals.len(),
+            self.num_mles,
+            "wrong number of evaluations"
+        );
+        self.terms
+            .iter()
+            .map(|(c, idx)| *c * idx.iter().map(|&i| mle_evals[i]).product::<F>())
+            .sum()
+    }
+
+    /// Absorb the composition so that challenges depend on the relation being proven
+    pub(crate) fn absorb(&self, transcript: &mut Transcript) {
+        transcript.append_u64(b"degree", self.degree() as u64);
+        transcript.append_u64(b"num_mles", self.num_mles as u64);
+        transcript.append_u64(b"num_terms", self.terms.len() as u64);
+        for (c, idx) in &self.terms {
+            transcript.append_field(b"coeff", c);
+            transcript.append_u64(b"term_len", idx.len() as u64);
+            for &i in idx {
+                transcript.append_u64(b"index", i as u64);
+            }
+        }
+    }
+}
+
+/// Sum of weighted products of multilinear polynomials sharing `n_vars` variables
+#[derive(Clone, Debug)]
+pub struct VirtualPoly<'a, F: PrimeField> {
+    /// Number of variables of every polynomial
+    pub n_vars: usize,
+    /// The composition function G
+    pub composition: Composition<F>,
+    /// Distinct polynomials referenced by the composition
+    pub mles: Vec<&'a MLPoly<F>>,
+}
+
+impl<'a, F: PrimeField> VirtualPoly<'a, F> {
+    /// Create an empty (zero) virtual polynomial
+    pub fn new(n_vars: usize) -> Self {
+        Self {
+            n_vars,
+            composition: Composition {
+                num_mles: 0,
+                terms: Vec::new(),
+            },
+            mles: Vec::new(),
+        }
+    }
+
+    /// Add the term coeff · ∏ product
+    ///
+    /// A polynomial passed by the same reference in several terms is stored once.
+    ///
+    /// # Panics
+    /// Panics if a factor has the wrong number of variables
+    pub fn add_product(&mut self, coeff: F, product: &[&'a MLPoly<F>]) {
+        let indices = product
+            .iter()
+            .map(|&f| {
+                assert_eq!(
+                    f.n_vars, self.n_vars,
+                    "factor has wrong number of variables"
+                );
+                match self.mles.iter().position(|&m| std::ptr::eq(m, f)) {
+                    Some(i) => i,
+                    None => {
+                        self.mles.push(f);
+                        self.mles.len() - 1
+                    }
+                }
+            })
+            .collect();
+        self.composition.num_mles = self.mles.len();
+        self.composition.terms.push((coeff, indices));
+    }
+
+    /// Degree of the composition
+    pub fn degree(&self) -> usize {
+        self.composition.degree()
+    }
+
+    /// Sum over the boolean hypercube: ∑_x G(f_1(x), ..., f_k(x))
+    pub fn sum_all(&self) -> F {
+        let mut evals = vec![F::ZERO; self.mles.len()];
+        (0..1usize << self.n_vars)
+            .map(|x| {
+                for (e, f) in evals.iter_mut().zip(&self.mles) {
+                    *e = f.evals[x];
+                }
+                self.composition.evaluate(&evals)
+            })
+            .sum()
+    }
+}
+
+/// Fill `v[t] = lo + t · (hi - lo)` for t = 0, ..., v.len() - 1
+#[inline]
+fn extend_line<F: PrimeField>(lo: F, hi: F, v: &mut [F]) {
+    let step = hi - lo;
+    v[0] = lo;
+    for t in 1..v.len() {
+        v[t] = v[t - 1] + step;
+    }
+}
+
+/// Add ∑_j c_j ∏_{i ∈ S_j} vals[i][t] to `sums[t]` for every t
+#[inline]
+fn accumulate_terms<F: PrimeField>(composition: &Composition<F>, vals: &[Vec<F>], sums: &mut [F]) {
+    for (c, idx) in &composition.terms {
+        for (t, sum) in sums.iter_mut().enumerate() {
+            *sum += *c * idx.iter().map(|&i| vals[i][t]).product::<F>();
+        }
+    }
+}
+
+/// Add the contribution of the pair (f_i(0, j), f_i(1, j)) to `sums`
+///
+/// `vals` is scratch space with `vals[i][t] = f_i(t, j)` on return.
+#[inline]
+fn accumulate_pair<F: PrimeField>(
+    composition: &Composition<F>,
+    mles: &[MLPoly<F>],
+    j: usize,
+    vals: &mut [Vec<F>],
+    sums: &mut [F],
+) {
+    for (f, v) in mles.iter().zip(vals.iter_mut()) {
+        extend_line(f.evals[2 * j], f.evals[2 * j + 1], v);
+    }
+    accumulate_terms(composition, vals, sums);
+}
+
+/// Compute [g(0), g(1), ..., g(d)] for the current round of a virtual polynomial sumcheck
+///
+/// Every f_i is linear in the bound variable, so its values at t = 0, ..., d are
+/// obtained by repeatedly adding f_i(1, ...) - f_i(0, ...).
+fn round_evals<F: PrimeField>(composition: &Composition<F>, mles: &[MLPoly<F>]) -> Vec<F> {
+    let degree = composition.degree();
+    let half = mles[0].len() / 2;
+
+    // Each worker keeps its own partial sums and scratch; field addition is exact,
+    // so the split does not change the result
+    #[cfg(feature = "parallel")]
+    let sums = (0..half)
+        .into_par_iter()
+        .fold(
+            || {
+                (
+                    vec![F::ZERO; degree + 1],
+                    vec![vec![F::ZERO; degree + 1]; mles.len()],
+                )
+            },
+            |(mut sums, mut vals), j| {
+                accumulate_pair(composition, mles, j, &mut vals, &mut sums);
+                (sums, vals)
+            },
+        )
+        .map(|(sums, _)| sums)
+        .reduce(
+            || vec![F::ZERO; degree + 1],
+            |mut a, b| {
+                a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                a
+            },
+        );
+
+    #[cfg(not(feature = "parallel"))]
+    let sums = {
+        let mut sums = vec![F::ZERO; degree + 1];
+        // vals[i][t] = f_i(t, x_2, ..., x_n)
+        let mut vals = vec![vec![F::ZERO; degree + 1]; mles.len()];
+        for j in 0..half {
+            accumulate_pair(composition, mles, j, &mut vals, &mut sums);
+        }
+        sums
+    };
+
+    sums
+}
+
+/// Bind the first variable of every f_i to r in place and return the next round's
+/// [g(0), ..., g(d)]
+///
+/// Equivalent to folding each f_i and calling `round_evals`, but every evaluation
+/// is read once per round: each group of four folds into the pair
+/// (f_i(r, 0, y), f_i(r, 1, y)), which is written back and added to the sums.
+fn fold_and_round_evals<F: PrimeField>(
+    composition: &Composition<F>,
+    mles: &mut [MLPoly<F>],
+    r: F,
+) -> Vec<F> {
+    let degree = composition.degree();
+    let quarter = mles[0].len() / 4;
+
+    // Pair j of f_i only overwrites groups below j, so group 0 is folded first and
+    // then each level of groups [m, 2m), which lies above every pair written so
+    // far; workers take blocks of a level and fold them in place
+    #[cfg(feature = "parallel")]
+    let sums = {
+        const BLOCK: usize = 1 << 10;
+        let mut sums = vec![F::ZERO; degree + 1];
+        let mut vals = vec![vec![F::ZERO; degree + 1]; mles.len()];
+        for (f, v) in mles.iter_mut().zip(vals.iter_mut()) {
+            let e = &mut f.evals;
+            let lo = e[0] + r * (e[1] - e[0]);
+            let hi = e[2] + r * (e[3] - e[2]);
+            e[0] = lo;
+            e[1] = hi;
+            extend_line(lo, hi, v);
+        }
+        accumulate_terms(composition, &vals, &mut sums);
+
+        let mut m = 1;
+        while m < quarter {
+            let mut blocks: Vec<Vec<(&[F], &mut [F])>> = (0..m.div_ceil(BLOCK))
+                .map(|_| Vec::with_capacity(mles.len()))
+                .collect();
+            for f in mles.iter_mut() {
+                let (pairs, groups) = f.evals.split_at_mut(4 * m);
+                let chunks = groups[..4 * m]
+                    .chunks(4 * BLOCK)
+                    .zip(pairs[2 * m..].chunks_mut(2 * BLOCK));
+                for (block, chunk) in blocks.iter_mut().zip(chunks) {
+                    block.push(chunk);
+                }
+            }
+            let level = blocks
+                .into_par_iter()
+                .map(|mut block| {
+                    let mut sums = vec![F::ZERO; degree + 1];
+                    let mut vals = vec![vec![F::ZERO; degree + 1]; block.len()];
+                    for j in 0..block[0].1.len() / 2 {
+                        for ((e, out), v) in block.iter_mut().zip(vals.iter_mut()) {
+                            let lo = e[4 * j] + r * (e[4 * j + 1] - e[4 * j]);
+                            let hi = e[4 * j + 2] + r * (e[4 * j + 3] - e[4 * j + 2]);
+                            out[2 * j] = lo;
+                            out[2 * j + 1] = hi;
+                            extend_line(lo, hi, v);
+                        }
+                        accumulate_terms(composition, &vals, &mut sums);
+                    }
+                    sums
+                })
+                .reduce(
+                    || vec![F::ZERO; degree + 1],
+                    |mut a, b| {
+                        a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                        a
+                    },
+                );
+            sums.iter_mut().zip(level).for_each(|(x, y)| *x += y);
+            m *= 2;
+        }
+        sums
+    };
+
+    // Slots 2j and 2j + 1 of f_i only read slots 4j, ..., 4j + 3, which are still intact
+    #[cfg(not(feature = "parallel"))]
+    let sums = {
+        let mut sums = vec![F::ZERO; degree + 1];
+        let mut vals = vec![vec![F::ZERO; degree + 1]; mles.len()];
+        for j in 0..quarter {
+            for (f, v) in mles.iter_mut().zip(vals.iter_mut()) {
+                let e = &mut f.evals;
+                let lo = e[4 * j] + r * (e[4 * j + 1] - e[4 * j]);
+                let hi = e[4 * j + 2] + r * (e[4 * j + 3] - e[4 * j + 2]);
+                e[2 * j] = lo;
+                e[2 * j + 1] = hi;
+                extend_line(lo, hi, v);
+            }
+            accumulate_terms(composition, &vals, &mut sums);
+        }
+        sums
+    };
+
+    for f in mles.iter_mut() {
+        f.evals.truncate(2 * quarter);
+        f.n_vars -= 1;
+    }
+    sums
+}
+
+/// Generate a sumcheck proof for ∑_x G(f_1(x), ..., f_k(x))
+///
+/// # Arguments
+/// * `stmt` - Public statement; `claim_sum` is the claimed sum of G over the hypercube
+/// * `poly` - The virtual polynomial; the round degree is derived from its composition
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The proof, the challenge point and the evaluation of every f_i at that point
+/// (in the order of `poly.mles`)
+pub fn prove_virtual<F: PrimeField>(
+    stmt: &Statement<F>,
+    poly: &VirtualPoly<'_, F>,
+    transcript: &mut Transcript,
+) -> ProverOutput<F> {
+    assert_eq!(
+        poly.n_vars, stmt.n_vars,
+        "virtual polynomial has wrong number of variables"
+    );
+    assert!(!poly.mles.is_empty(), "virtual polynomial has no terms");
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+    poly.composition.absorb(transcript);
+
+    let mut current: Vec<MLPoly<F>> = poly.mles.iter().map(|&f| f.clone()).collect();
+    let mut round_polys = Vec::with_capacity(stmt.n_vars);
+    let mut point = Vec::with_capacity(stmt.n_vars);
+
+    // Only round 1 needs a separate pass; later evaluations fall out of the previous fold
+    let mut evals = if stmt.n_vars > 0 {
+        round_evals(&poly.composition, &current)
+    } else {
+        Vec::new()
+    };
+
+    for i in 0..stmt.n_vars {
+        let round_poly = RoundPoly::from_evals(evals);
+        append_round_poly(&round_poly, transcript);
+        round_polys.push(round_poly);
+
+        let r: F = transcript.challenge_scalar(b"r");
+        point.push(r);
+
+        // Bind x_i = r in every polynomial
+        if i + 1 < stmt.n_vars {
+            evals = fold_and_round_evals(&poly.composition, &mut current, r);
+        } else {
+            current.iter_mut().for_each(|f| f.fold_first_var_in_place(r));
+            evals = Vec::new();
+        }
+    }
+
+    ProverOutput {
+        proof: SumcheckProof { round_polys },
+        point,
+        final_evals: current.iter().map(|f| f.evals[0]).collect(),
+    }
+}
+
+/// Run the round checks of a virtual-polynomial sumcheck and return the final claim
+///
+/// Replays the transcript of `prove_virtual` (statement, then composition) and
+/// leaves the final check G(f_1(r), ..., f_k(r)) == `expected_eval` to callers
+/// that derive the f_i(r) themselves instead of querying oracles.
+pub(crate) fn verify_virtual_rounds<F: PrimeField>(
+    stmt: &Statement<F>,
+    composition: &Composition<F>,
+    proof: &SumcheckProof<F>,
+    transcript: &mut Transcript,
+) -> Result<SubClaim<F>> {
+    absorb_statement(stmt, &SumcheckConfig::default(), transcript);
+    composition.absorb(transcript);
+    verify_rounds(
+        stmt.claim_sum,
+        stmt.n_vars,
+        composition.degree(),
+        proof,
+        transcript,
+    )
+}
+
+/// Verify a sumcheck proof for ∑_x G(f_1(x), ..., f_k(x))
+///
+/// # Arguments
+/// * `stmt` - Public statement containing n_vars and claimed sum
+/// * `composition` - The composition G, as used by the prover
+/// * `proof` - The sumcheck proof to verify
+/// * `oracles` - One oracle per f_i, in the order of the composition's indices
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final check G(f_1(r), ..., f_k(r)) == claim fails
+/// * `Err(_)` if the shapes disagree or a round check fails
+pub fn verify_virtual<F: PrimeField>(
+    stmt: &Statement<F>,
+    composition: &Composition<F>,
+    proof: &SumcheckProof<F>,
+    oracles: &[&dyn Oracle<F>],
+    transcript: &mut Transcript,
+) -> Result<bool> {
+    if oracles.len() != composition.num_mles {
+        return Err(Error::DimensionMismatch("wrong number of oracles"));
+    }
+    let subclaim = verify_virtual_rounds(stmt, composition, proof, transcript)?;
+
+    // Final check: G(f_1(r), ..., f_k(r)) == final claim
+    let mle_evals = oracles
+        .iter()
+        .map(|o| o.query(&subclaim.point))
+        .collect::<Result<Vec<F>>>()?;
+    Ok(composition.evaluate(&mle_evals) == subclaim.expected_eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    fn random_poly(n_vars: usize, rng: &mut impl ark_std::rand::Rng) -> MLPoly<Fr> {
+        let evals: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(rng)).collect();
+        MLPoly { n_vars, evals }
+    }
+
+    fn oracles_for(poly: &VirtualPoly<'_, Fr>) -> Vec<PolyOracle<Fr>> {
+        poly.mles
+            .iter()
+            .map(|&f| PolyOracle::new(f.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_product_deduplicates() {
+        let mut rng = ark_std::test_rng();
+        let a = random_poly(2, &mut rng);
+        let b = random_poly(2, &mut rng);
+
+        let mut poly = VirtualPoly::new(2);
+        poly.add_product(Fr::from(1u64), &[&a, &b]);
+        poly.add_product(Fr::from(2u64), &[&a, &a, &b]);
+
+        assert_eq!(poly.mles.len(), 2);
+        assert_eq!(poly.degree(), 3);
+        assert_eq!(poly.composition.terms[1].1, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn test_round_evals_match_direct_evaluation() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 9;
+        let a = random_poly(n_vars, &mut rng);
+        let b = random_poly(n_vars, &mut rng);
+        let c = random_poly(n_vars, &mut rng);
+
+        let mut poly = VirtualPoly::new(n_vars);
+        poly.add_product(Fr::from(3u64), &[&a, &b, &c]);
+        poly.add_product(-Fr::from(1u64), &[&c]);
+        let mles: Vec<MLPoly<Fr>> = poly.mles.iter().map(|&f| f.clone()).collect();
+
+        // g(t) = ∑_y G(f_1(t, y), ..., f_k(t, y)), with each f_i fixed at t by folding
+        let evals = round_evals(&poly.composition, &mles);
+        for (t, e) in evals.iter().enumerate() {
+            let fixed: Vec<MLPoly<Fr>> = mles
+                .iter()
+                .map(|f| f.fold_first_var(Fr::from(t as u64)))
+                .collect();
+            let direct: Fr = (0..fixed[0].len())
+                .map(|y| {
+                    let point: Vec<Fr> = fixed.iter().map(|f| f.evals[y]).collect();
+                    poly.composition.evaluate(&point)
+                })
+                .sum();
+            assert_eq!(*e, direct, "g({}) differs", t);
+        }
+    }
+
+    #[test]
+    fn test_fold_and_round_evals_matches_two_passes() {
+        let mut rng = ark_std::test_rng();
+        // Large enough for the parallel build to split the first rounds into several blocks
+        let n_vars = 13;
+        let a = random_poly(n_vars, &mut rng);
+        let b = random_poly(n_vars, &mut rng);
+
+        let mut poly = VirtualPoly::new(n_vars);
+        poly.add_product(Fr::from(2u64), &[&a, &b, &b]);
+        poly.add_product(Fr::from(5u64), &[&a]);
+
+        let mut fused: Vec<MLPoly<Fr>> = poly.mles.iter().map(|&f| f.clone()).collect();
+        let mut two_pass = fused.clone();
+        for _ in 0..n_vars - 1 {
+            let r = Fr::rand(&mut rng);
+            let evals = fold_and_round_evals(&poly.composition, &mut fused, r);
+            two_pass.iter_mut().for_each(|f| f.fold_first_var_in_place(r));
+            assert_eq!(fused, two_pass);
+            assert_eq!(evals, round_evals(&poly.composition, &two_pass));
+        }
+    }
+
+    #[test]
+    fn test_virtual_sumcheck_honest_prover() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 4;
+
+        // eq · (a·b − c), with eq standing in for any multilinear weight
+        let eq = random_poly(n_vars, &mut rng);
+        let a = random_poly(n_vars, &mut rng);
+        let b = random_poly(n_vars, &mut rng);
+        let c = random_poly(n_vars, &mut rng);
+        let mut poly = VirtualPoly::new(n_vars);
+        poly.add_product(Fr::from(1u64), &[&eq, &a, &b]);
+        poly.add_product(-Fr::from(1u64), &[&eq, &c]);
+
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let mut transcript = Transcript::new(b"virtual-test");
+        let out = prove_virtual(&stmt, &poly, &mut transcript);
+        assert!(out.proof.round_polys.iter().all(|g| g.degree() == 3));
+
+        let oracles = oracles_for(&poly);
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"virtual-test");
+        let result = verify_virtual(&stmt, &poly.composition, &out.proof, &refs, &mut transcript);
+
+        assert!(result.unwrap(), "honest proof should verify");
+    }
+
+    #[test]
+    fn test_virtual_wrong_composition_rejected() {
+        let mut rng = ark_std::test_rng();
+        let n_vars = 3;
+
+        let a = random_poly(n_vars, &mut rng);
+        let b = random_poly(n_vars, &mut rng);
+        let mut poly = VirtualPoly::new(n_vars);
+        poly.add_product(Fr::from(3u64), &[&a, &b]);
+        poly.add_product(Fr::from(1u64), &[&b]);
+
+        let stmt = Statement {
+            n_vars,
+            claim_sum: poly.sum_all(),
+        };
+        let mut transcript = Transcript::new(b"virtual-test");
+        let out = prove_virtual(&stmt, &poly, &mut transcript);
+
+        // Same degree, different coefficient
+        let mut other = poly.composition.clone();
+        other.terms[0].0 = Fr::from(4u64);
+
+        let oracles = oracles_for(&poly);
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"virtual-test");
+        let result = verify_virtual(&stmt, &other, &out.proof, &refs, &mut transcript);
+
+        assert_ne!(result, Ok(true));
+    }
+}
@@ -0,0 +1,199 @@
+//! Zerocheck: prove that a virtual polynomial vanishes on the whole hypercube
+//!
+//! To show G(f_1(x), ..., f_k(x)) = 0 for every x ∈ {0,1}^n, sample τ from the
+//! transcript and run the sumcheck for ∑_x eq(τ, x) · G(f_1(x), ..., f_k(x)) = 0.
+//! The sum is the multilinear extension of G(...) evaluated at τ, which is zero
+//! with overwhelming probability only if G vanishes everywhere on the hypercube.
+
+use ark_ff::PrimeField;
+use mlpoly::{eq_eval, MLPoly};
+
+use crate::error::{Error, Result};
+use crate::oracle::Oracle;
+use crate::transcript::Transcript;
+use crate::types::{ProverOutput, Statement, SubClaim, SumcheckProof};
+use crate::virtual_poly::{prove_virtual, verify_virtual_rounds, Composition, VirtualPoly};
+
+/// Sample τ ∈ F^n from the transcript
+pub(crate) fn sample_tau<F: PrimeField>(n_vars: usize, transcript: &mut Transcript) -> Vec<F> {
+    transcript.append_message(b"protocol", b"zerocheck");
+    transcript.append_u64(b"n_vars", n_vars as u64);
+    (0..n_vars)
+        .map(|_| transcript.challenge_scalar(b"tau"))
+        .collect()
+}
+
+/// The composition eq · G, with eq placed at index 0 and the f_i shifted by one
+pub(crate) fn with_eq<F: PrimeField>(composition: &Composition<F>) -> Composition<F> {
+    Composition {
+        num_mles: composition.num_mles + 1,
+        terms: composition
+            .terms
+            .iter()
+            .map(|(c, idx)| {
+                let mut shifted = Vec::with_capacity(idx.len() + 1);
+                shifted.push(0);
+                shifted.extend(idx.iter().map(|&i| i + 1));
+                (*c, shifted)
+            })
+            .collect(),
+    }
+}
+
+/// Generate a zerocheck proof that `poly` vanishes on {0,1}^n
+///
+/// # Arguments
+/// * `poly` - The virtual polynomial G(f_1, ..., f_k)
+/// * `transcript` - Fiat-Shamir transcript for challenge generation
+///
+/// # Returns
+/// The sumcheck proof of ∑_x eq(τ, x) · G(...) = 0, the challenge point and the
+/// evaluation of every f_i at that point (in the order of `poly.mles`)
+pub fn prove_zerocheck<F: PrimeField>(
+    poly: &VirtualPoly<'_, F>,
+    transcript: &mut Transcript,
+) -> ProverOutput<F> {
+    let tau: Vec<F> = sample_tau(poly.n_vars, transcript);
+    let eq = MLPoly::eq_table(&tau);
+
+    let mut mles = Vec::with_capacity(poly.mles.len() + 1);
+    mles.push(&eq);
+    mles.extend(poly.mles.iter().copied());
+    let weighted = VirtualPoly {
+        n_vars: poly.n_vars,
+        composition: with_eq(&poly.composition),
+        mles,
+    };
+
+    let stmt = Statement {
+        n_vars: poly.n_vars,
+        claim_sum: F::ZERO,
+    };
+    let mut out = prove_virtual(&stmt, &weighted, transcript);
+    // Drop eq(τ, r): the verifier computes it itself
+    out.final_evals.remove(0);
+    out
+}
+
+/// Run the round checks of a zerocheck and return τ with the final claim
+///
+/// Replays the transcript of `prove_zerocheck`; the caller checks
+/// eq(τ, r) · G(f_1(r), ..., f_k(r)) against `expected_eval`.
+pub(crate) fn verify_zerocheck_rounds<F: PrimeField>(
+    n_vars: usize,
+    composition: &Composition<F>,
+    proof: &SumcheckProof<F>,
+    transcript: &mut Transcript,
+) -> Result<(Vec<F>, SubClaim<F>)> {
+    let tau: Vec<F> = sample_tau(n_vars, transcript);
+    let stmt = Statement {
+        n_vars,
+        claim_sum: F::ZERO,
+    };
+    let subclaim = verify_virtual_rounds(&stmt, &with_eq(composition), proof, transcript)?;
+    Ok((tau, subclaim))
+}
+
+/// Verify a zerocheck proof
+///
+/// # Arguments
+/// * `n_vars` - Number of variables of the f_i
+/// * `composition` - The composition G, as used by the prover
+/// * `proof` - The sumcheck proof to verify
+/// * `oracles` - One oracle per f_i, in the order of the composition's indices
+/// * `transcript` - Fiat-Shamir transcript (must use same domain as prover)
+///
+/// # Returns
+/// * `Ok(true)` if the proof is valid
+/// * `Ok(false)` if the final oracle check fails
+/// * `Err(_)` if the shapes disagree or a round check fails
+pub fn verify_zerocheck<F: PrimeField>(
+    n_vars: usize,
+    composition: &Composition<F>,
+    proof: &SumcheckProof<F>,
+    oracles: &[&dyn Oracle<F>],
+    transcript: &mut Transcript,
+) -> Result<bool> {
+    if oracles.len() != composition.num_mles {
+        return Err(Error::DimensionMismatch("wrong number of oracles"));
+    }
+    let (tau, subclaim) = verify_zerocheck_rounds(n_vars, composition, proof, transcript)?;
+
+    // Final check: eq(τ, r) · G(f_1(r), ..., f_k(r)) == final claim
+    let mle_evals = oracles
+        .iter()
+        .map(|o| o.query(&subclaim.point))
+        .collect::<Result<Vec<F>>>()?;
+    let eq = eq_eval(&tau, &subclaim.point);
+    Ok(eq * composition.evaluate(&mle_evals) == subclaim.expected_eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::PolyOracle;
+    use ark_bn254::Fr;
+    use ark_std::UniformRand;
+
+    /// a · b − c with c = a ∘ b, optionally corrupted at one index
+    fn constraint_polys(n_vars: usize, corrupt: Option<usize>) -> [MLPoly<Fr>; 3] {
+        let mut rng = ark_std::test_rng();
+        let a: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let b: Vec<Fr> = (0..(1 << n_vars)).map(|_| Fr::rand(&mut rng)).collect();
+        let mut c: Vec<Fr> = a.iter().zip(&b).map(|(x, y)| *x * y).collect();
+        if let Some(i) = corrupt {
+            c[i] += Fr::from(1u64);
+        }
+        [
+            MLPoly::from_evals(a),
+            MLPoly::from_evals(b),
+            MLPoly::from_evals(c),
+        ]
+    }
+
+    fn run(polys: &[MLPoly<Fr>; 3]) -> Result<bool> {
+        let [a, b, c] = polys;
+        let n_vars = a.n_vars;
+        let mut poly = VirtualPoly::new(n_vars);
+        poly.add_product(Fr::from(1u64), &[a, b]);
+        poly.add_product(-Fr::from(1u64), &[c]);
+
+        let mut transcript = Transcript::new(b"zerocheck-test");
+        let out = prove_zerocheck(&poly, &mut transcript);
+
+        for (f, e) in poly.mles.iter().zip(&out.final_evals) {
+            assert_eq!(f.eval_at(&out.point), *e);
+        }
+
+        let oracles: Vec<PolyOracle<Fr>> = poly
+            .mles
+            .iter()
+            .map(|&f| PolyOracle::new(f.clone()))
+            .collect();
+        let refs: Vec<&dyn Oracle<Fr>> = oracles.iter().map(|o| o as &dyn Oracle<Fr>).collect();
+        let mut transcript = Transcript::new(b"zerocheck-test");
+        verify_zerocheck(
+            n_vars,
+            &poly.composition,
+            &out.proof,
+            &refs,
+            &mut transcript,
+        )
+    }
+
+    #[test]
+    fn test_zerocheck_satisfied() {
+        let polys = constraint_polys(4, None);
+        assert!(run(&polys).unwrap(), "vanishing polynomial should verify");
+    }
+
+    #[test]
+    fn test_zerocheck_violated_constraint_fails() {
+        let polys = constraint_polys(4, Some(5));
+        assert_ne!(
+            run(&polys),
+            Ok(true),
+            "a single nonzero entry must be caught"
+        );
+    }
+}